@@ -91,7 +91,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -146,7 +147,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -196,7 +198,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -250,7 +253,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -299,7 +303,8 @@ pub fn resize_image(
                 destination_size,
                 16,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -354,7 +359,8 @@ pub fn resize_image(
                 destination_size,
                 16,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -403,7 +409,8 @@ pub fn resize_image(
                 destination_size,
                 16,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0);
 
             match color_space {
@@ -458,7 +465,8 @@ pub fn resize_image(
                 destination_size,
                 16,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
 
             match color_space {
                 ColorSpace::Gamma => {
@@ -505,7 +513,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0.);
 
             match color_space {
@@ -559,7 +568,8 @@ pub fn resize_image(
                 source_size,
                 destination_size,
                 resampling_function,
-            )?;
+            )
+            .map_err(|e| e.to_string())?;
             working_vec.resize(0, 0.);
 
             match color_space {
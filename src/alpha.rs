@@ -467,7 +467,7 @@ pub fn premultiply_luma_alpha_f32(in_place: &mut [f32]) {
     for chunk in in_place.chunks_exact_mut(2) {
         let a = chunk[1];
         chunk[0] *= a;
-        chunk[2] = a;
+        chunk[1] = a;
     }
 }
 
@@ -485,7 +485,7 @@ pub fn premultiplied_luma_alpha_f32(source: &[f32]) -> Vec<f32> {
     // Almost all loops are not auto-vectorised without doing anything dirty.
     // So everywhere is just added something beautiful.
     for (dst, src) in target.chunks_exact_mut(2).zip(source.chunks_exact(2)) {
-        let a = src[2];
+        let a = src[1];
         dst[0] = src[0] * a;
         dst[1] = a;
     }
@@ -554,3 +554,108 @@ pub fn unpremultiply_luma_alpha_f32(in_place: &mut [f32]) {
         }
     }
 }
+
+/// Associate alpha in place
+///
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `in_place`: Slice to where premultiply
+///
+#[cfg(feature = "half")]
+pub fn premultiply_rgba_f16(in_place: &mut [half::f16]) {
+    for chunk in in_place.chunks_exact_mut(4) {
+        let a = chunk[3].to_f32();
+        chunk[0] = half::f16::from_f32(chunk[0].to_f32() * a);
+        chunk[1] = half::f16::from_f32(chunk[1].to_f32() * a);
+        chunk[2] = half::f16::from_f32(chunk[2].to_f32() * a);
+    }
+}
+
+/// Associate alpha in place
+///
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `in_place`: Slice to where premultiply
+///
+#[cfg(feature = "half")]
+pub fn premultiply_luma_alpha_f16(in_place: &mut [half::f16]) {
+    for chunk in in_place.chunks_exact_mut(2) {
+        let a = chunk[1].to_f32();
+        chunk[0] = half::f16::from_f32(chunk[0].to_f32() * a);
+    }
+}
+
+/// Associate alpha to a new destination
+///
+/// Faster, if you need to do a copy first
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `source`: Source slice with luma alpha
+///
+#[cfg(feature = "half")]
+pub fn premultiplied_luma_alpha_f16(source: &[half::f16]) -> Vec<half::f16> {
+    let mut target = source.to_vec();
+    premultiply_luma_alpha_f16(&mut target);
+    target
+}
+
+/// Associate alpha to a new destination
+///
+/// Faster, if you need to do a copy first
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `source`: Source rgba slice
+///
+#[cfg(feature = "half")]
+pub fn premultiplied_rgba_f16(source: &[half::f16]) -> Vec<half::f16> {
+    let mut target = source.to_vec();
+    premultiply_rgba_f16(&mut target);
+    target
+}
+
+/// Un-premultiply alpha in place
+///
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `in_place`: Slice to work on
+///
+#[cfg(feature = "half")]
+pub fn unpremultiply_rgba_f16(in_place: &mut [half::f16]) {
+    for chunk in in_place.chunks_exact_mut(4) {
+        let a = chunk[3].to_f32();
+        if a != 0. {
+            let a_recip = 1. / a;
+            chunk[0] = half::f16::from_f32(chunk[0].to_f32() * a_recip);
+            chunk[1] = half::f16::from_f32(chunk[1].to_f32() * a_recip);
+            chunk[2] = half::f16::from_f32(chunk[2].to_f32() * a_recip);
+        }
+    }
+}
+
+/// Un-premultiply alpha in place
+///
+/// Note, for scaling alpha must be *associated*
+///
+/// # Arguments
+///
+/// * `in_place`: Slice to work on
+///
+#[cfg(feature = "half")]
+pub fn unpremultiply_luma_alpha_f16(in_place: &mut [half::f16]) {
+    for chunk in in_place.chunks_exact_mut(2) {
+        let a = chunk[1].to_f32();
+        if a != 0. {
+            chunk[0] = half::f16::from_f32(chunk[0].to_f32() / a);
+        }
+    }
+}
@@ -26,6 +26,7 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::image_size::ImageSize;
 use num_traits::AsPrimitive;
 use std::ops::{AddAssign, BitXor};
 
@@ -107,6 +108,34 @@ pub fn has_non_constant_alpha_luma_alpha_f32(store: &[f32], width: usize) -> boo
     has_non_constant_alpha_f32_impl::<1, 2>(store, width)
 }
 
+/// Performs scan on the RGBA f16 image if it has variable alpha channel
+///
+/// # Arguments
+///
+/// * `store`: Slice with rgba f16 data
+/// * `width`: image width
+///
+/// returns: bool
+///
+#[cfg(feature = "half")]
+pub fn has_non_constant_alpha_rgba_f16(store: &[half::f16], width: usize) -> bool {
+    has_non_constant_alpha_f16_impl::<3, 4>(store, width)
+}
+
+/// Performs scan on the Luma-alpha f16 image if it has variable alpha channel
+///
+/// # Arguments
+///
+/// * `store`: Slice with Luma alpha f16 data
+/// * `width`: image width
+///
+/// returns: bool
+///
+#[cfg(feature = "half")]
+pub fn has_non_constant_alpha_luma_alpha_f16(store: &[half::f16], width: usize) -> bool {
+    has_non_constant_alpha_f16_impl::<1, 2>(store, width)
+}
+
 #[inline]
 fn has_non_constant_alpha<
     V: Copy + PartialEq + BitXor<V, Output = V> + 'static + AsPrimitive<J> + 'static,
@@ -126,7 +155,7 @@ where
     if store.is_empty() {
         return false;
     }
-    let first = store[0];
+    let first = store[ALPHA_CHANNEL_INDEX];
     let mut row_sums: J = 0u32.as_();
     for row in store.chunks_exact(width * CHANNELS) {
         for color in row.chunks_exact(CHANNELS) {
@@ -152,7 +181,7 @@ fn has_non_constant_alpha_f32_impl<const ALPHA_CHANNEL_INDEX: usize, const CHANN
     if store.is_empty() {
         return false;
     }
-    let first = store[0].to_bits();
+    let first = store[ALPHA_CHANNEL_INDEX].to_bits();
     let mut row_sums: u64 = 0u64;
     for row in store.chunks_exact(width * CHANNELS) {
         for color in row.chunks_exact(CHANNELS) {
@@ -167,3 +196,45 @@ fn has_non_constant_alpha_f32_impl<const ALPHA_CHANNEL_INDEX: usize, const CHANN
 
     row_sums.ne(&zeros)
 }
+
+#[inline]
+#[cfg(feature = "half")]
+fn has_non_constant_alpha_f16_impl<const ALPHA_CHANNEL_INDEX: usize, const CHANNELS: usize>(
+    store: &[half::f16],
+    width: usize,
+) -> bool {
+    assert!(ALPHA_CHANNEL_INDEX < CHANNELS);
+    assert!(CHANNELS > 0 && CHANNELS <= 4);
+    if store.is_empty() {
+        return false;
+    }
+    let first = store[ALPHA_CHANNEL_INDEX].to_bits();
+    let mut row_sums: u64 = 0u64;
+    for row in store.chunks_exact(width * CHANNELS) {
+        for color in row.chunks_exact(CHANNELS) {
+            row_sums += color[ALPHA_CHANNEL_INDEX].to_bits().bitxor(first) as u64;
+        }
+        if row_sums != 0 {
+            return true;
+        }
+    }
+
+    row_sums != 0
+}
+
+/// Checks if alpha, considered as the last channel, is the same across the whole image
+pub(crate) fn has_constant_alpha<T: Copy + PartialEq, const CHANNELS: usize>(
+    store: &[T],
+    stride: usize,
+    size: ImageSize,
+) -> bool {
+    let row_length = size.width * CHANNELS;
+    let Some(&first) = store.get(CHANNELS - 1) else {
+        return true;
+    };
+    store.chunks(stride).take(size.height).all(|row| {
+        row[..row_length]
+            .chunks_exact(CHANNELS)
+            .all(|color| color[CHANNELS - 1] == first)
+    })
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::alpha::{
+    premultiply_la16, premultiply_la8, premultiply_luma_alpha_f32, premultiply_rgba16,
+    premultiply_rgba8, premultiply_rgba_f32, unpremultiply_la16, unpremultiply_la8,
+    unpremultiply_luma_alpha_f32, unpremultiply_rgba16, unpremultiply_rgba8,
+    unpremultiply_rgba_f32,
+};
+#[cfg(feature = "half")]
+use crate::alpha::{
+    premultiply_luma_alpha_f16, premultiply_rgba_f16, unpremultiply_luma_alpha_f16,
+    unpremultiply_rgba_f16,
+};
+use crate::row_transform::RowTransform;
+
+/// Associates alpha when row is loaded and un-associates it when row is stored
+///
+/// Alpha is always considered as the last channel.
+pub(crate) struct AlphaTransform<const CHANNELS: usize> {
+    pub(crate) bit_depth: u32,
+}
+
+/// Clamps colors to alpha, because ringing filters may produce associated colors above it
+fn clamp_to_alpha<T: Copy + Ord, const CHANNELS: usize>(src: &[T], dst: &mut [T]) {
+    for (dst, src) in dst
+        .chunks_exact_mut(CHANNELS)
+        .zip(src.chunks_exact(CHANNELS))
+    {
+        let a = src[CHANNELS - 1];
+        for (dst, &src) in dst.iter_mut().zip(src[..CHANNELS - 1].iter()) {
+            *dst = src.min(a);
+        }
+        dst[CHANNELS - 1] = a;
+    }
+}
+
+impl RowTransform<u8, u8> for AlphaTransform<4> {
+    fn load(&self, src: &[u8], dst: &mut [u8]) {
+        dst.copy_from_slice(src);
+        premultiply_rgba8(dst);
+    }
+
+    fn store(&self, src: &[u8], dst: &mut [u8]) {
+        clamp_to_alpha::<u8, 4>(src, dst);
+        unpremultiply_rgba8(dst);
+    }
+}
+
+impl RowTransform<u8, u8> for AlphaTransform<2> {
+    fn load(&self, src: &[u8], dst: &mut [u8]) {
+        dst.copy_from_slice(src);
+        premultiply_la8(dst);
+    }
+
+    fn store(&self, src: &[u8], dst: &mut [u8]) {
+        clamp_to_alpha::<u8, 2>(src, dst);
+        unpremultiply_la8(dst);
+    }
+}
+
+impl RowTransform<u16, u16> for AlphaTransform<4> {
+    fn load(&self, src: &[u16], dst: &mut [u16]) {
+        dst.copy_from_slice(src);
+        premultiply_rgba16(dst, self.bit_depth);
+    }
+
+    fn store(&self, src: &[u16], dst: &mut [u16]) {
+        clamp_to_alpha::<u16, 4>(src, dst);
+        unpremultiply_rgba16(dst, self.bit_depth);
+    }
+}
+
+impl RowTransform<u16, u16> for AlphaTransform<2> {
+    fn load(&self, src: &[u16], dst: &mut [u16]) {
+        dst.copy_from_slice(src);
+        premultiply_la16(dst, self.bit_depth);
+    }
+
+    fn store(&self, src: &[u16], dst: &mut [u16]) {
+        clamp_to_alpha::<u16, 2>(src, dst);
+        unpremultiply_la16(dst, self.bit_depth);
+    }
+}
+
+impl RowTransform<f32, f32> for AlphaTransform<4> {
+    fn load(&self, src: &[f32], dst: &mut [f32]) {
+        dst.copy_from_slice(src);
+        premultiply_rgba_f32(dst);
+    }
+
+    fn store(&self, src: &[f32], dst: &mut [f32]) {
+        dst.copy_from_slice(src);
+        unpremultiply_rgba_f32(dst);
+    }
+}
+
+impl RowTransform<f32, f32> for AlphaTransform<2> {
+    fn load(&self, src: &[f32], dst: &mut [f32]) {
+        dst.copy_from_slice(src);
+        premultiply_luma_alpha_f32(dst);
+    }
+
+    fn store(&self, src: &[f32], dst: &mut [f32]) {
+        dst.copy_from_slice(src);
+        unpremultiply_luma_alpha_f32(dst);
+    }
+}
+
+#[cfg(feature = "half")]
+impl RowTransform<half::f16, half::f16> for AlphaTransform<4> {
+    fn load(&self, src: &[half::f16], dst: &mut [half::f16]) {
+        dst.copy_from_slice(src);
+        premultiply_rgba_f16(dst);
+    }
+
+    fn store(&self, src: &[half::f16], dst: &mut [half::f16]) {
+        dst.copy_from_slice(src);
+        unpremultiply_rgba_f16(dst);
+    }
+}
+
+#[cfg(feature = "half")]
+impl RowTransform<half::f16, half::f16> for AlphaTransform<2> {
+    fn load(&self, src: &[half::f16], dst: &mut [half::f16]) {
+        dst.copy_from_slice(src);
+        premultiply_luma_alpha_f16(dst);
+    }
+
+    fn store(&self, src: &[half::f16], dst: &mut [half::f16]) {
+        dst.copy_from_slice(src);
+        unpremultiply_luma_alpha_f16(dst);
+    }
+}
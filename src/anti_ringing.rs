@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::filter_weights::FilterBounds;
+use crate::mixed_storage::RoundingBackend;
+
+/// Anti-ringing clamp of a single pass
+#[derive(Debug, Clone)]
+pub(crate) struct AntiRinging {
+    /// How far output is pulled into the range of the nearest taps, `0..=1`
+    pub strength: f32,
+    /// Nearest taps of each output sample, relative to start of its filter bounds
+    pub taps: Vec<FilterBounds>,
+}
+
+/// Sample type which may be pulled towards clamped value
+pub(crate) trait AntiRingingSample: Copy + PartialOrd {
+    /// Moves sample towards `clamped` by `strength`
+    fn mix_towards(self, clamped: Self, strength: f32) -> Self;
+}
+
+macro_rules! integer_anti_ringing_sample {
+    ($sample_type:ty) => {
+        impl AntiRingingSample for $sample_type {
+            #[inline(always)]
+            fn mix_towards(self, clamped: Self, strength: f32) -> Self {
+                let value = self as f64;
+                (value + (clamped as f64 - value) * strength as f64).cpu_round() as $sample_type
+            }
+        }
+    };
+}
+
+macro_rules! float_anti_ringing_sample {
+    ($sample_type:ty) => {
+        impl AntiRingingSample for $sample_type {
+            #[inline(always)]
+            fn mix_towards(self, clamped: Self, strength: f32) -> Self {
+                self + (clamped - self) * strength as $sample_type
+            }
+        }
+    };
+}
+
+integer_anti_ringing_sample!(u8);
+integer_anti_ringing_sample!(u16);
+integer_anti_ringing_sample!(u32);
+float_anti_ringing_sample!(f32);
+float_anti_ringing_sample!(f64);
+
+#[cfg(feature = "half")]
+impl AntiRingingSample for half::f16 {
+    #[inline(always)]
+    fn mix_towards(self, clamped: Self, strength: f32) -> Self {
+        let value = self.to_f32();
+        half::f16::from_f32(value + (clamped.to_f32() - value) * strength)
+    }
+}
+
+#[inline(always)]
+fn clamp_sample<T: AntiRingingSample>(value: T, lo: T, hi: T, strength: f32) -> T {
+    let clamped = if value < lo {
+        lo
+    } else if value > hi {
+        hi
+    } else {
+        return value;
+    };
+    if strength >= 1. {
+        clamped
+    } else {
+        value.mix_towards(clamped, strength)
+    }
+}
+
+/// Clamps one row produced by horizontal pass, `src` is the row given to the row handler
+pub(crate) fn anti_ring_row<T: AntiRingingSample, const CHANNELS: usize>(
+    src: &[T],
+    dst: &mut [T],
+    bounds: &[FilterBounds],
+    anti_ringing: &AntiRinging,
+) {
+    for ((dst, bounds), taps) in dst
+        .chunks_exact_mut(CHANNELS)
+        .zip(bounds.iter())
+        .zip(anti_ringing.taps.iter())
+    {
+        if taps.size == 0 {
+            continue;
+        }
+        let start = (bounds.start + taps.start) * CHANNELS;
+        let pixels = &src[start..start + taps.size * CHANNELS];
+        for (c, dst) in dst.iter_mut().enumerate() {
+            let mut lo = pixels[c];
+            let mut hi = pixels[c];
+            for pixel in pixels.chunks_exact(CHANNELS).skip(1) {
+                let v = pixel[c];
+                if v < lo {
+                    lo = v;
+                }
+                if v > hi {
+                    hi = v;
+                }
+            }
+            *dst = clamp_sample(*dst, lo, hi, anti_ringing.strength);
+        }
+    }
+}
+
+/// Clamps rows produced by horizontal pass over `src` with `src_stride`,
+/// does nothing when anti-ringing is not requested
+pub(crate) fn anti_ring_rows<T: AntiRingingSample, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    bounds: &[FilterBounds],
+    anti_ringing: Option<&AntiRinging>,
+) {
+    let Some(anti_ringing) = anti_ringing else {
+        return;
+    };
+    for (src, dst) in src.chunks(src_stride).zip(dst.chunks_mut(dst_stride)) {
+        anti_ring_row::<T, CHANNELS>(src, dst, bounds, anti_ringing);
+    }
+}
+
+/// Clamps one row produced by vertical pass, arguments are the ones given to the column handler
+pub(crate) fn anti_ring_column<T: AntiRingingSample>(
+    bounds: &FilterBounds,
+    taps: &FilterBounds,
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    strength: f32,
+) {
+    if taps.size == 0 {
+        return;
+    }
+    let first = (bounds.start + taps.start) * src_stride;
+    for (x, dst) in dst.iter_mut().enumerate() {
+        let mut lo = src[first + x];
+        let mut hi = lo;
+        for j in 1..taps.size {
+            let v = src[first + j * src_stride + x];
+            if v < lo {
+                lo = v;
+            }
+            if v > hi {
+                hi = v;
+            }
+        }
+        *dst = clamp_sample(*dst, lo, hi, strength);
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use std::ops::Range;
+
+/// Part of L2 cache the column pass is allowed to fill with source rows
+const L2_CACHE_BUDGET: usize = 256 * 1024;
+
+/// Strip widths are kept multiple of the widest column handler block
+const STRIP_ALIGNMENT: usize = 64;
+
+/// Vertical strips the column pass walks through.
+///
+/// Each output row reads `kernel_size` full source rows, which for wide images
+/// with large kernels don't fit in cache and are evicted before neighbouring
+/// output rows get to reuse them. Splitting image into strips keeps rows
+/// of one strip small enough to stay in L2 between output rows.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ColumnTiles {
+    row_length: usize,
+    strip_width: usize,
+}
+
+impl ColumnTiles {
+    /// Image is tiled only when working set of one output row exceeds cache budget
+    pub(crate) fn new<T>(row_length: usize, kernel_size: usize) -> ColumnTiles {
+        let row_bytes = row_length * size_of::<T>();
+        if row_bytes * kernel_size <= L2_CACHE_BUDGET {
+            return ColumnTiles {
+                row_length,
+                strip_width: row_length.max(1),
+            };
+        }
+        let strip_width = L2_CACHE_BUDGET / (kernel_size.max(1) * size_of::<T>());
+        let strip_width = (strip_width / STRIP_ALIGNMENT * STRIP_ALIGNMENT).max(STRIP_ALIGNMENT);
+        ColumnTiles {
+            row_length,
+            strip_width,
+        }
+    }
+
+    /// Ranges of row elements, left to right
+    pub(crate) fn strips(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        (0..self.row_length)
+            .step_by(self.strip_width)
+            .map(|start| start..(start + self.strip_width).min(self.row_length))
+    }
+
+    /// Rows handed to one worker, so that each worker walks strips
+    /// over its own band of output rows
+    #[cfg(feature = "rayon")]
+    pub(crate) fn band_height(&self, height: usize) -> usize {
+        if self.strip_width >= self.row_length {
+            return 1;
+        }
+        height.div_ceil(rayon::current_num_threads() * 4).max(1)
+    }
+}
@@ -26,18 +26,120 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::anti_ringing::AntiRinging;
+use crate::edge_mode::EdgeMode;
+use crate::error::PicScaleError;
 use crate::filter_weights::{FilterBounds, FilterWeights};
 use crate::math::{ConstPI, ConstSqrt2, Jinc};
-use crate::sampler::ResamplingFunction;
+use crate::resize_options::ResizeOptions;
+use crate::source_rect::AxisMapping;
 use num_traits::{AsPrimitive, Float, Signed};
 use std::fmt::Debug;
 use std::ops::{AddAssign, Div, MulAssign, Neg};
 
+/// Most taps a filter might have for a single destination pixel
+pub(crate) const MAX_KERNEL_SIZE: usize = 1 << 20;
+
+/// Normalizes window of weights starting at `start` and clips it if edge mode requires so
+///
+/// Returns start and size of the taps written into `dst`,
+/// start is negative or beyond the image only for edge modes which need padding.
+fn store_window<T>(
+    window: &[T],
+    start: i64,
+    weights_sum: T,
+    in_size: usize,
+    edge_mode: EdgeMode,
+    dst: &mut [T],
+) -> (i64, usize)
+where
+    T: Copy + Float + 'static,
+    f32: AsPrimitive<T>,
+{
+    let (skip, start, size) = match edge_mode {
+        EdgeMode::Transparent => {
+            let clipped_start = start.max(0).min(in_size as i64);
+            let clipped_end = (start + window.len() as i64).min(in_size as i64);
+            (
+                (clipped_start - start) as usize,
+                clipped_start,
+                (clipped_end - clipped_start).max(0) as usize,
+            )
+        }
+        _ => (0, start, window.len()),
+    };
+
+    if weights_sum != 0f32.as_() {
+        let recpeq = 1f32.as_() / weights_sum;
+
+        for (dst, src) in dst.iter_mut().zip(window.iter().skip(skip).take(size)) {
+            *dst = *src * recpeq;
+        }
+    }
+
+    (start, size)
+}
+
+/// Converts window positions into bounds, for edge modes with padding
+/// bounds are shifted into coordinates of the padded image
+///
+/// Windows far outside the image are folded towards it first, so padding is bounded
+/// by kernel and image size rather than by the mapping offset.
+fn finalize_weights<T>(
+    weights: Vec<T>,
+    kernel_size: usize,
+    filter_radius: i32,
+    windows: &[(i64, usize)],
+    in_size: usize,
+    edge_mode: EdgeMode,
+) -> FilterWeights<T> {
+    let windows = windows
+        .iter()
+        .map(|&(start, size)| match size {
+            // Handlers read the first row of bounds even without taps
+            0 => (0, 0),
+            _ => (edge_mode.fold_window(start, size, in_size), size),
+        })
+        .collect::<Vec<_>>();
+    let padding = if edge_mode.needs_padding() {
+        windows
+            .iter()
+            .map(|&(start, size)| (-start).max(start + size as i64 - in_size as i64))
+            .max()
+            .unwrap_or(0)
+            .max(0) as usize
+    } else {
+        0
+    };
+    let bounds = windows
+        .iter()
+        .map(|&(start, size)| FilterBounds::new((start + padding as i64) as usize, size))
+        .collect::<Vec<_>>();
+    FilterWeights::<T>::new(
+        weights,
+        kernel_size,
+        kernel_size,
+        windows.len(),
+        filter_radius,
+        bounds,
+    )
+    .with_edge_mode(edge_mode, padding)
+}
+
+/// Generates weights for resampling `in_size` into `out_size`
+///
+/// # Arguments
+///
+/// * `mapping`: Placement of destination in source, `None` stretches destination over whole source
+/// * `options`: Filter, edge mode, blur, taper and anti-ringing are taken from here
+///
+/// Returns [PicScaleError::KernelTooLarge] when filter exceeds [MAX_KERNEL_SIZE] taps.
 pub(crate) fn generate_weights<T>(
-    function: ResamplingFunction,
     in_size: usize,
     out_size: usize,
-) -> FilterWeights<T>
+    mapping: Option<AxisMapping>,
+    options: &ResizeOptions,
+) -> Result<FilterWeights<T>, PicScaleError>
 where
     T: Copy
         + Neg
@@ -62,29 +164,50 @@ where
     i32: AsPrimitive<T>,
     usize: AsPrimitive<T>,
 {
-    let resampling_filter = function.get_resampling_filter();
-    let scale = in_size.as_() / out_size.as_();
+    let edge_mode = options.edge_mode;
+    let resampling_filter = options.resampling_function.get_resampling_filter();
+    let (scale, offset): (T, T) = match mapping {
+        None => (in_size.as_() / out_size.as_(), 0f32.as_()),
+        Some(mapping) => (mapping.scale.as_(), mapping.offset.as_()),
+    };
     let is_resizable_kernel = resampling_filter.is_resizable_kernel;
     let filter_scale_cutoff = match is_resizable_kernel {
         true => scale.max(1f32.as_()),
         false => 1f32.as_(),
     };
     let filter_base_size = resampling_filter.min_kernel_size * 2.;
-    let resampling_function = resampling_filter.kernel;
-    let window_func = resampling_filter.window;
+    let resampling_function = &resampling_filter.kernel;
+    let window_func = resampling_filter.window.as_ref();
 
-    let mut bounds: Vec<FilterBounds> = vec![FilterBounds::new(0, 0); out_size];
+    let mut windows: Vec<(i64, usize)> = vec![(0, 0); out_size];
 
     let is_area = resampling_filter.is_area_filter && scale < 1.as_();
 
     if !is_area {
-        let base_size: usize = (filter_base_size.as_() * filter_scale_cutoff).round().as_();
+        let is_kernel_shaped = options.is_kernel_shaped();
+        let blur: T = options.blur.as_();
+        let taper: T = options.taper.as_();
+        // Support of the kernel before blur in source pixels
+        let support: T = resampling_filter.min_kernel_size.as_() * filter_scale_cutoff;
+        let base_size: usize = (filter_base_size.as_() * filter_scale_cutoff * blur)
+            .round()
+            .max(1f32.as_())
+            .as_();
         let kernel_size = base_size;
+        if kernel_size > MAX_KERNEL_SIZE {
+            return Err(PicScaleError::KernelTooLarge(kernel_size));
+        }
         let filter_radius = base_size.as_() / 2.as_();
         let filter_scale = 1f32.as_() / filter_scale_cutoff;
         let mut weights: Vec<T> = vec![T::default(); kernel_size * out_size];
         let mut local_filters = vec![T::default(); kernel_size];
         let mut filter_position = 0usize;
+        // Taps closer than one kernel unit to the center are bounding anti-ringing clamp
+        let mut nearest_taps = if options.anti_ringing > 0. {
+            vec![FilterBounds::new(0, 0); out_size]
+        } else {
+            Vec::new()
+        };
         let blur_scale = match window_func {
             None => 1f32.as_(),
             Some(window) => {
@@ -96,21 +219,45 @@ where
             }
         };
 
-        for (i, bound) in bounds.iter_mut().enumerate() {
-            let center_x = ((i.as_() + 0.5.as_()) * scale).min(in_size.as_());
+        for (i, window) in windows.iter_mut().enumerate() {
+            let mut center_x = (i.as_() + 0.5.as_()) * scale + offset;
             let mut weights_sum: T = 0f32.as_();
 
-            let start: usize = (center_x - filter_radius).floor().max(0f32.as_()).as_();
-            let end: usize = (center_x + filter_radius)
-                .ceil()
-                .min(in_size.as_())
-                .min(start.as_() + kernel_size.as_())
-                .as_();
-
+            let (start, end): (i64, i64) = if edge_mode == EdgeMode::Shrink {
+                center_x = center_x.min(in_size.as_()).max(0f32.as_());
+                let start: usize = (center_x - filter_radius).floor().max(0f32.as_()).as_();
+                let end: usize = (center_x + filter_radius)
+                    .ceil()
+                    .min(in_size.as_())
+                    .min(start.as_() + kernel_size.as_())
+                    .as_();
+                (start as i64, end as i64)
+            } else {
+                // Window is not cut here, edge mode decides what to do with taps outside
+                let start: i64 = (center_x - filter_radius).floor().as_();
+                let end: i64 = (center_x + filter_radius)
+                    .ceil()
+                    .min((start + kernel_size as i64).as_())
+                    .as_();
+                (start, end)
+            };
             let center = center_x - 0.5.as_();
+            let mut nearest: (i64, i64) = (end, start);
 
             for (local_filter_iteration, k) in (start..end).enumerate() {
-                let dx = k.as_() - center;
+                let mut dx = k.as_() - center;
+                if dx.abs() * filter_scale < 1f32.as_() {
+                    nearest = (nearest.0.min(k), nearest.1.max(k + 1));
+                }
+                if is_kernel_shaped {
+                    let x = dx.abs() / blur;
+                    let flat = taper * support;
+                    dx = if x <= flat {
+                        0f32.as_()
+                    } else {
+                        (x - flat) / (1f32.as_() - taper)
+                    };
+                }
                 let weight;
                 if let Some(resampling_window) = window_func {
                     let mut x = dx.abs();
@@ -125,7 +272,7 @@ where
                         (x - resampling_window.taper.as_())
                             / (1f32.as_() - resampling_window.taper.as_())
                     };
-                    let window_producer = resampling_window.window;
+                    let window_producer = &resampling_window.window;
                     let x_kernel_scaled = x * filter_scale;
                     let window = if x < resampling_window.window_size.as_() {
                         window_producer(x_kernel_scaled * resampling_window.window_size.as_())
@@ -141,34 +288,46 @@ where
                 local_filters[local_filter_iteration] = weight;
             }
 
-            let size = end - start;
+            let size = (end - start) as usize;
 
-            *bound = FilterBounds::new(start, size);
+            *window = store_window(
+                &local_filters[..size],
+                start,
+                weights_sum,
+                in_size,
+                edge_mode,
+                &mut weights[filter_position..filter_position + kernel_size],
+            );
 
-            if weights_sum != 0f32.as_() {
-                let recpeq = 1f32.as_() / weights_sum;
-
-                for (dst, src) in weights
-                    .iter_mut()
-                    .skip(filter_position)
-                    .take(size)
-                    .zip(local_filters.iter().take(size))
-                {
-                    *dst = *src * recpeq;
+            if let Some(taps) = nearest_taps.get_mut(i) {
+                let (start, size) = *window;
+                let near_start = nearest.0.max(start);
+                let near_end = nearest.1.min(start + size as i64);
+                if near_end > near_start {
+                    *taps = FilterBounds::new(
+                        (near_start - start) as usize,
+                        (near_end - near_start) as usize,
+                    );
                 }
             }
 
             filter_position += kernel_size;
         }
 
-        FilterWeights::<T>::new(
+        let anti_ringing = (!nearest_taps.is_empty()).then_some(AntiRinging {
+            strength: options.anti_ringing,
+            taps: nearest_taps,
+        });
+
+        Ok(finalize_weights(
             weights,
             kernel_size,
-            kernel_size,
-            out_size,
             filter_radius.as_(),
-            bounds,
+            &windows,
+            in_size,
+            edge_mode,
         )
+        .with_anti_ringing(anti_ringing))
     } else {
         // Simulating INTER_AREA from OpenCV, for up scaling here,
         // this is necessary because weight computation is different
@@ -180,11 +339,11 @@ where
         let mut local_filters = vec![T::default(); kernel_size];
         let mut filter_position = 0usize;
 
-        for (i, bound) in bounds.iter_mut().enumerate() {
+        for (i, window) in windows.iter_mut().enumerate() {
             let mut weights_sum: T = 0f32.as_();
 
-            let sx: T = (i.as_() * scale).floor();
-            let fx = (i as i64 + 1).as_() - (sx + 1.as_()) * inv_scale;
+            let sx: T = (i.as_() * scale + offset).floor();
+            let fx = (i as i64 + 1).as_() - (sx + 1.as_() - offset) * inv_scale;
             let dx = if fx <= 0.as_() {
                 0.as_()
             } else {
@@ -196,46 +355,50 @@ where
             local_filters[0] = weight0;
             local_filters[1] = weight1;
 
-            let start: usize = sx.floor().max(0f32.as_()).as_();
-            let end: usize = (sx + kernel_size.as_())
-                .ceil()
-                .min(in_size.as_())
-                .min(start.as_() + kernel_size.as_())
-                .as_();
+            let (start, end): (i64, i64) = if edge_mode == EdgeMode::Shrink {
+                let sx = sx.min((in_size as i64 - 1).as_()).max(0f32.as_());
+                let start: i64 = sx.floor().max(0f32.as_()).as_();
+                let end: i64 = (sx + kernel_size.as_())
+                    .ceil()
+                    .min(in_size.as_())
+                    .min((start + kernel_size as i64).as_())
+                    .as_();
+                (start, end)
+            } else {
+                let start: i64 = sx.as_();
+                (start, start + kernel_size as i64)
+            };
 
-            let size = end - start;
+            let size = (end - start) as usize;
 
             weights_sum += weight0;
             if size > 1 {
                 weights_sum += weight1;
             }
-            *bound = FilterBounds::new(start, size);
-
-            if weights_sum != 0f32.as_() {
-                let recpeq = 1f32.as_() / weights_sum;
-
-                for (dst, src) in weights
-                    .iter_mut()
-                    .skip(filter_position)
-                    .take(size)
-                    .zip(local_filters.iter().take(size))
-                {
-                    *dst = *src * recpeq;
-                }
-            } else {
-                weights[filter_position] = 1.as_();
+
+            let dst = &mut weights[filter_position..filter_position + kernel_size];
+            *window = store_window(
+                &local_filters[..size],
+                start,
+                weights_sum,
+                in_size,
+                edge_mode,
+                dst,
+            );
+            if weights_sum == 0f32.as_() {
+                dst[0] = 1.as_();
             }
 
             filter_position += kernel_size;
         }
 
-        FilterWeights::new(
+        Ok(finalize_weights(
             weights,
             kernel_size,
-            kernel_size,
-            out_size,
             filter_radius.as_(),
-            bounds,
-        )
+            &windows,
+            in_size,
+            edge_mode,
+        ))
     }
 }
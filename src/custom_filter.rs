@@ -0,0 +1,258 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::error::PicScaleError;
+use crate::sampler::{ResamplingFilter, ResamplingWindow, MAX_KERNEL_RADIUS};
+use num_traits::AsPrimitive;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Window applied on top of [CustomFilter] kernel
+///
+/// Window function is identified by [CustomFilter::id], so windows are compared
+/// and hashed by parameters only.
+#[derive(Debug, Copy, Clone)]
+pub struct CustomWindow {
+    /// Window function, receives distance normalized to window support, in range `0..1`
+    pub window: fn(f32) -> f32,
+    /// Support of the window
+    pub window_size: f32,
+    /// Kernel support multiplier, `1` keeps kernel as is, bigger values are blurring
+    pub blur: f32,
+    /// Distance from the center in range `0..1` where kernel is flat
+    pub taper: f32,
+}
+
+impl CustomWindow {
+    /// Creates window with no blur and no taper
+    pub fn new(window: fn(f32) -> f32, window_size: f32) -> CustomWindow {
+        CustomWindow {
+            window,
+            window_size,
+            blur: 1.,
+            taper: 0.,
+        }
+    }
+}
+
+impl PartialEq for CustomWindow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CustomWindow {}
+
+impl PartialOrd for CustomWindow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parameters are compared by [f32::total_cmp]
+impl Ord for CustomWindow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.window_size
+            .total_cmp(&other.window_size)
+            .then(self.blur.total_cmp(&other.blur))
+            .then(self.taper.total_cmp(&other.taper))
+    }
+}
+
+impl Hash for CustomWindow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.window_size.to_bits().hash(state);
+        self.blur.to_bits().hash(state);
+        self.taper.to_bits().hash(state);
+    }
+}
+
+/// User defined resampling filter
+///
+/// Non-capturing closures might be used as kernel and window.
+///
+/// Function addresses are not reliable to tell functions apart, so filters are compared,
+/// ordered and hashed by user supplied [CustomFilter::id] and parameters.
+/// Different kernel or window functions must be given different ids.
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{resize_rgb8, CustomFilter, ImageSize, ResamplingFunction};
+///
+/// // Triangle filter
+/// let filter = CustomFilter::new(1, |x| (1. - x.abs()).max(0.), 1.);
+/// let source = vec![0u8; 64 * 64 * 3];
+/// let resized = resize_rgb8(
+///     &source,
+///     ImageSize::new(64, 64),
+///     ImageSize::new(32, 32),
+///     ResamplingFunction::Custom(filter),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct CustomFilter {
+    /// Identifies kernel and window functions
+    pub id: u64,
+    /// Kernel, receives distance from the sample in source pixels
+    pub kernel: fn(f32) -> f32,
+    /// Support radius of the kernel, must be in range `0..=64`, zero excluded
+    pub radius: f32,
+    /// Optional window, see [CustomWindow] for more info
+    pub window: Option<CustomWindow>,
+    /// If `true` kernel is stretched when downscaling, so it works as a low-pass filter.
+    /// Set to `false` for kernels which must always be sampled at integral distances.
+    pub is_resizable_kernel: bool,
+}
+
+impl CustomFilter {
+    /// Creates resizable kernel without window
+    ///
+    /// # Arguments
+    ///
+    /// * `id`: Identifies kernel and window functions, see [CustomFilter] for more info
+    /// * `kernel`: Kernel, receives distance from the sample in source pixels
+    /// * `radius`: Support radius of the kernel
+    ///
+    pub fn new(id: u64, kernel: fn(f32) -> f32, radius: f32) -> CustomFilter {
+        CustomFilter {
+            id,
+            kernel,
+            radius,
+            window: None,
+            is_resizable_kernel: true,
+        }
+    }
+
+    /// Sets window, see [CustomWindow] for more info
+    pub fn with_window(self, window: CustomWindow) -> CustomFilter {
+        CustomFilter {
+            window: Some(window),
+            ..self
+        }
+    }
+
+    /// Sets whether kernel is stretched when downscaling
+    pub fn with_resizable_kernel(self, is_resizable_kernel: bool) -> CustomFilter {
+        CustomFilter {
+            is_resizable_kernel,
+            ..self
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), PicScaleError> {
+        let positive = |v: f32| v.is_finite() && v > 0.;
+        let valid_window = match self.window {
+            None => true,
+            Some(window) => {
+                positive(window.window_size)
+                    && window.blur.is_finite()
+                    && window.blur >= 0.
+                    && window.taper.is_finite()
+                    && (0. ..1.).contains(&window.taper)
+            }
+        };
+        if !positive(self.radius) || self.radius > MAX_KERNEL_RADIUS || !valid_window {
+            return Err(PicScaleError::InvalidResamplingFunction);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn resampling_filter<T>(&self) -> ResamplingFilter<T>
+    where
+        T: Copy + 'static + AsPrimitive<f64>,
+        f64: AsPrimitive<T>,
+    {
+        let kernel = self.kernel;
+        ResamplingFilter {
+            kernel: Box::new(move |x: T| eval(kernel, x)),
+            window: self.window.map(|window| {
+                let window_fn = window.window;
+                // Window producer is called with distance multiplied by window size
+                let normalize = 1. / (window.window_size as f64 * window.window_size as f64);
+                ResamplingWindow::new(
+                    move |x: T| {
+                        let x: f64 = x.as_();
+                        eval(window_fn, x * normalize)
+                    },
+                    window.window_size,
+                    window.blur,
+                    window.taper,
+                )
+            }),
+            min_kernel_size: self.radius,
+            is_resizable_kernel: self.is_resizable_kernel,
+            is_area_filter: false,
+        }
+    }
+}
+
+/// User functions are always evaluated in `f32`
+fn eval<T>(function: fn(f32) -> f32, x: impl AsPrimitive<f64>) -> T
+where
+    T: Copy + 'static,
+    f64: AsPrimitive<T>,
+{
+    let x: f64 = x.as_();
+    (function(x as f32) as f64).as_()
+}
+
+impl PartialEq for CustomFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CustomFilter {}
+
+impl PartialOrd for CustomFilter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Functions are compared by id, parameters by [f32::total_cmp]
+impl Ord for CustomFilter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id
+            .cmp(&other.id)
+            .then(self.radius.total_cmp(&other.radius))
+            .then(self.window.cmp(&other.window))
+            .then(self.is_resizable_kernel.cmp(&other.is_resizable_kernel))
+    }
+}
+
+impl Hash for CustomFilter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.radius.to_bits().hash(state);
+        self.window.hash(state);
+        self.is_resizable_kernel.hash(state);
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::filter_weights::FilterBounds;
+use crate::threading::Executor;
+use crate::validation::scratch_slice;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Describes how pixels outside the image are sampled when kernel crosses the edge
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum EdgeMode {
+    /// Kernel is cut at the edge and remaining weights are renormalized
+    #[default]
+    Shrink,
+    /// Edge pixel is repeated, `aaa|abcd|ddd`
+    Clamp,
+    /// Image is mirrored around the edge, `cba|abcd|dcb`
+    Reflect,
+    /// Image is tiled, `bcd|abcd|abc`
+    Wrap,
+    /// Pixels outside are zero, weights are not renormalized so edges fade out.
+    ///
+    /// For images with alpha this is meaningful only for premultiplied data.
+    Transparent,
+}
+
+impl EdgeMode {
+    /// Modes which are sampling pixels outside the image, those are executed on padded rows
+    pub(crate) const fn needs_padding(self) -> bool {
+        matches!(self, EdgeMode::Clamp | EdgeMode::Reflect | EdgeMode::Wrap)
+    }
+
+    /// Maps coordinate which may lay outside the image into `0..size`
+    pub(crate) fn map(self, x: i64, size: usize) -> usize {
+        let size = size as i64;
+        let mapped = match self {
+            EdgeMode::Reflect => {
+                let period = size * 2;
+                let x = x.rem_euclid(period);
+                if x < size {
+                    x
+                } else {
+                    period - 1 - x
+                }
+            }
+            EdgeMode::Wrap => x.rem_euclid(size),
+            EdgeMode::Shrink | EdgeMode::Clamp | EdgeMode::Transparent => x.max(0).min(size - 1),
+        };
+        mapped as usize
+    }
+
+    /// Moves window of `size` taps starting at `start` as close to the image as possible
+    /// without changing pixels it reads, so padding doesn't grow with the distance from the image
+    pub(crate) fn fold_window(self, start: i64, size: usize, in_size: usize) -> i64 {
+        let in_size = in_size as i64;
+        match self {
+            EdgeMode::Clamp => start.max(-(size as i64)).min(in_size),
+            EdgeMode::Reflect => start.rem_euclid(in_size * 2),
+            EdgeMode::Wrap => start.rem_euclid(in_size),
+            EdgeMode::Shrink | EdgeMode::Transparent => start,
+        }
+    }
+}
+
+/// Fills `padding` pixels at both sides of the row, image pixels must be already in the middle
+pub(crate) fn fill_row_padding<T: Copy, const CHANNELS: usize>(
+    row: &mut [T],
+    width: usize,
+    padding: usize,
+    edge_mode: EdgeMode,
+) {
+    for x in 0..padding {
+        let left = edge_mode.map(x as i64 - padding as i64, width) + padding;
+        row.copy_within(left * CHANNELS..(left + 1) * CHANNELS, x * CHANNELS);
+        let right = edge_mode.map((width + x) as i64, width) + padding;
+        let dst = (padding + width + x) * CHANNELS;
+        row.copy_within(right * CHANNELS..(right + 1) * CHANNELS, dst);
+    }
+}
+
+/// Horizontal pass over rows padded according to edge mode
+///
+/// Each source row is copied into a padded row before `handler` is called, so weights are
+/// expected to be generated in padded coordinates.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn convolve_padded_rows<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    width: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    padding: usize,
+    edge_mode: EdgeMode,
+    handler: impl Fn(&[T], &mut [T]) + Sync,
+    executor: &Executor,
+) where
+    T: Copy + Default + Send + Sync,
+{
+    let row_length = width * CHANNELS;
+    let padded_length = (width + 2 * padding) * CHANNELS;
+
+    let pass = |src: &[T], dst: &mut [T], row: &mut Vec<T>| {
+        row[padding * CHANNELS..padding * CHANNELS + row_length]
+            .copy_from_slice(&src[..row_length]);
+        fill_row_padding::<T, CHANNELS>(row, width, padding, edge_mode);
+        handler(row, dst);
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            src.par_chunks(src_stride)
+                .zip(dst.par_chunks_mut(dst_stride))
+                .with_min_len(executor.min_len(width))
+                .for_each_init(
+                    || vec![T::default(); padded_length],
+                    |row, (src, dst)| pass(src, dst, row),
+                );
+        });
+    } else {
+        let mut row = vec![T::default(); padded_length];
+        for (src, dst) in src.chunks(src_stride).zip(dst.chunks_mut(dst_stride)) {
+            pass(src, dst, &mut row);
+        }
+    }
+}
+
+/// Resolves vertical bounds given in padded coordinates against the image
+///
+/// When all rows are inside the image, the image itself is returned,
+/// otherwise required rows are gathered into `rows`.
+///
+/// Returns bounds, source and stride for column handler.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn column_source<'a, T: Copy + Default>(
+    bounds: &FilterBounds,
+    src: &'a [T],
+    src_stride: usize,
+    row_length: usize,
+    height: usize,
+    padding: usize,
+    edge_mode: EdgeMode,
+    rows: &'a mut Vec<T>,
+) -> (FilterBounds, &'a [T], usize) {
+    if bounds.start >= padding && bounds.start + bounds.size <= height + padding {
+        return (
+            FilterBounds::new(bounds.start - padding, bounds.size),
+            src,
+            src_stride,
+        );
+    }
+    let rows = scratch_slice(rows, bounds.size * row_length);
+    for (j, dst) in rows.chunks_exact_mut(row_length).enumerate() {
+        let y = edge_mode.map((bounds.start + j) as i64 - padding as i64, height);
+        dst.copy_from_slice(&src[y * src_stride..y * src_stride + row_length]);
+    }
+    (FilterBounds::new(0, bounds.size), rows, row_length)
+}
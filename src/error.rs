@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::compute_weights::MAX_KERNEL_SIZE;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Expected and actually received values of mismatched sizes
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct MismatchedSize {
+    pub expected: usize,
+    pub received: usize,
+}
+
+/// Errors which might occur while scaling
+///
+/// New variants might be added with new features, so matches must have a wildcard arm.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PicScaleError {
+    /// Width or height of the image is zero
+    ZeroImageDimensions,
+    /// `width * channels` or `stride * height` is overflowing `usize`
+    ImageSizeOverflow,
+    /// Stride is smaller than `width * channels`
+    InvalidStride(MismatchedSize),
+    /// Source slice is shorter than `stride * (height - 1) + width * channels`,
+    /// or source row pushed into [crate::StreamingResizer] is not `width * channels`
+    SourceSizeMismatch(MismatchedSize),
+    /// Destination slice is shorter than `stride * (height - 1) + width * channels`
+    DestinationSizeMismatch(MismatchedSize),
+    /// Bit-depth is not supported, for integral images it must be in range 1..=16
+    UnsupportedBitDepth(u32),
+    /// Channels count is not supported, image must have at least one channel,
+    /// and at most [crate::ChannelLayout::MAX_CHANNELS] for [crate::ResizePlan]
+    UnsupportedChannelsCount(usize),
+    /// Source rectangle is empty, not finite or not inside the source image
+    InvalidSourceRect,
+    /// Scale transform is not finite, has scale below [crate::ScaleTransform::MIN_SCALE]
+    /// or is combined with source rectangle
+    InvalidScaleTransform,
+    /// Parameters of resampling function, blur, taper or anti-ringing are not finite or out of range,
+    /// or elliptical weighted averaging is requested for a filter which is not jinc-based
+    InvalidResamplingFunction,
+    /// Plan can't be executed row by row, elliptical weighted averaging needs 2D neighbourhood
+    UnsupportedStreaming,
+    /// Threads count of [crate::ThreadingPolicy::Threads] is zero, or the pool can't be created
+    InvalidThreading,
+    /// Row is pushed into [crate::StreamingResizer] after all source rows, holds source height
+    TooManyRows(usize),
+    /// Filter would have more than 2^20 taps for a single destination pixel,
+    /// holds required taps count
+    KernelTooLarge(usize),
+    /// Interlaced frame has less than 2 rows, so one of fields is empty, holds frame height
+    InterlacedTooShort(usize),
+}
+
+impl Display for PicScaleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PicScaleError::ZeroImageDimensions => f.write_str("Image size must not be zero"),
+            PicScaleError::ImageSizeOverflow => {
+                f.write_str("Image size must never exceed usize::MAX")
+            }
+            PicScaleError::InvalidStride(size) => f.write_fmt(format_args!(
+                "Stride must be at least width * channels ({}) but got {}",
+                size.expected, size.received
+            )),
+            PicScaleError::SourceSizeMismatch(size) => f.write_fmt(format_args!(
+                "Source slice size mismatch, expected {} but got {}",
+                size.expected, size.received
+            )),
+            PicScaleError::DestinationSizeMismatch(size) => f.write_fmt(format_args!(
+                "Destination slice size mismatch, expected {} but got {}",
+                size.expected, size.received
+            )),
+            PicScaleError::UnsupportedBitDepth(bit_depth) => f.write_fmt(format_args!(
+                "Bit depth must be in range 1..=16 but got {}",
+                bit_depth
+            )),
+            PicScaleError::UnsupportedChannelsCount(channels) => f.write_fmt(format_args!(
+                "Channels count must be at least 1, and at most 8 for resize plans, but got {}",
+                channels
+            )),
+            PicScaleError::InvalidSourceRect => {
+                f.write_str("Source rectangle must be non-empty and inside the source image")
+            }
+            PicScaleError::InvalidResamplingFunction => {
+                f.write_str("Resampling function parameters must be finite and in range")
+            }
+            PicScaleError::UnsupportedStreaming => {
+                f.write_str("Elliptical weighted averaging can't be executed row by row")
+            }
+            PicScaleError::InvalidThreading => {
+                f.write_str("Thread pool must have at least one thread and be possible to create")
+            }
+            PicScaleError::TooManyRows(height) => f.write_fmt(format_args!(
+                "All {} source rows are already pushed",
+                height
+            )),
+            PicScaleError::KernelTooLarge(taps) => f.write_fmt(format_args!(
+                "Filter must have at most {} taps but got {}",
+                MAX_KERNEL_SIZE, taps
+            )),
+            PicScaleError::InterlacedTooShort(height) => f.write_fmt(format_args!(
+                "Interlaced frame must have at least 2 rows but got {}",
+                height
+            )),
+            PicScaleError::InvalidScaleTransform => f.write_str(
+                "Scale transform must be finite with scale of at least 1/4096 and not combined with source rectangle",
+            ),
+        }
+    }
+}
+
+impl Error for PicScaleError {}
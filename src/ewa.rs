@@ -0,0 +1,390 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::compute_weights::MAX_KERNEL_SIZE;
+use crate::edge_mode::EdgeMode;
+use crate::error::PicScaleError;
+use crate::filter_weights::FilterBounds;
+use crate::image_size::ImageSize;
+use crate::math::polar::{
+    polar_ginseng, polar_haasn_soft, polar_lanczos, JINC_RADIUS_2, JINC_RADIUS_3, JINC_RADIUS_4,
+    JINC_RADIUS_6,
+};
+use crate::mixed_storage::MixedStorage;
+use crate::resize_options::ResizeOptions;
+use crate::sampler::ResamplingFunction;
+use crate::source_rect::AxisMapping;
+use crate::threading::Executor;
+use crate::validation::{check_destination_layout, check_image_layout};
+use num_traits::AsPrimitive;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+
+/// Count of kernel samples in lookup table over squared radius
+const LUT_SIZE: usize = 4096;
+
+/// Radially symmetric kernel and its support in kernel units
+#[derive(Debug, Copy, Clone)]
+struct PolarKernel {
+    kernel: fn(f64, f64) -> f64,
+    radius: f64,
+}
+
+/// Polar counterpart of jinc-based filter, `None` for filters which are not jinc-based
+fn polar_kernel(resampling_function: ResamplingFunction) -> Option<PolarKernel> {
+    let lanczos = |radius| {
+        Some(PolarKernel {
+            kernel: polar_lanczos,
+            radius,
+        })
+    };
+    match resampling_function {
+        ResamplingFunction::Lanczos2Jinc => lanczos(JINC_RADIUS_2),
+        ResamplingFunction::Lanczos3Jinc => lanczos(JINC_RADIUS_3),
+        ResamplingFunction::Lanczos4Jinc => lanczos(JINC_RADIUS_4),
+        ResamplingFunction::Lanczos6Jinc => lanczos(JINC_RADIUS_6),
+        ResamplingFunction::Ginseng => Some(PolarKernel {
+            kernel: |x, _| polar_ginseng(x),
+            radius: JINC_RADIUS_3,
+        }),
+        ResamplingFunction::HaasnSoft => Some(PolarKernel {
+            kernel: |x, _| polar_haasn_soft(x),
+            radius: JINC_RADIUS_3 * 1.11,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks whether filter might be evaluated with elliptical weighted averaging
+pub(crate) fn is_polar_filter(resampling_function: ResamplingFunction) -> bool {
+    polar_kernel(resampling_function).is_some()
+}
+
+/// Source sample of one axis, `None` is outside the image and contributes zero
+#[derive(Debug, Copy, Clone)]
+struct EwaTap {
+    source: Option<usize>,
+    /// Distance to the sample position in kernel units
+    distance: f32,
+}
+
+/// Taps of every destination sample along one axis
+#[derive(Debug, Clone)]
+struct EwaAxis {
+    taps: Vec<EwaTap>,
+    bounds: Vec<FilterBounds>,
+}
+
+impl EwaAxis {
+    fn new(
+        in_size: usize,
+        out_size: usize,
+        mapping: Option<AxisMapping>,
+        support: f64,
+        edge_mode: EdgeMode,
+    ) -> Result<EwaAxis, PicScaleError> {
+        let (scale, offset) = match mapping {
+            None => (in_size as f64 / out_size as f64, 0.),
+            Some(mapping) => (mapping.scale, mapping.offset),
+        };
+        // Kernel is stretched over source when downscaling
+        let filter_scale = scale.max(1.);
+        let reach = support * filter_scale;
+        let kernel_size = ((reach * 2.).ceil() as usize).saturating_add(1);
+        if kernel_size > MAX_KERNEL_SIZE {
+            return Err(PicScaleError::KernelTooLarge(kernel_size));
+        }
+        let mut taps = Vec::new();
+        let mut bounds = Vec::with_capacity(out_size);
+        for i in 0..out_size {
+            let mut center_x = (i as f64 + 0.5) * scale + offset;
+            if edge_mode == EdgeMode::Shrink {
+                center_x = center_x.min(in_size as f64).max(0.);
+            }
+            let center = center_x - 0.5;
+            let start = taps.len();
+            let first = (center - reach).floor() as i64;
+            let last = (center + reach).ceil() as i64;
+            for k in first..=last {
+                let distance = (k as f64 - center) / filter_scale;
+                if distance.abs() >= support {
+                    continue;
+                }
+                let source = if k >= 0 && k < in_size as i64 {
+                    Some(k as usize)
+                } else {
+                    match edge_mode {
+                        EdgeMode::Shrink => continue,
+                        EdgeMode::Transparent => None,
+                        EdgeMode::Clamp | EdgeMode::Reflect | EdgeMode::Wrap => {
+                            Some(edge_mode.map(k, in_size))
+                        }
+                    }
+                };
+                taps.push(EwaTap {
+                    source,
+                    distance: distance as f32,
+                });
+            }
+            bounds.push(FilterBounds::new(start, taps.len() - start));
+        }
+        Ok(EwaAxis { taps, bounds })
+    }
+
+    fn taps(&self, i: usize) -> &[EwaTap] {
+        let bounds = self.bounds[i];
+        &self.taps[bounds.start..bounds.start + bounds.size]
+    }
+}
+
+/// Precomputed elliptical weighted averaging
+///
+/// Kernel is evaluated over the distance in 2D neighbourhood instead of two separable passes,
+/// distances are measured in kernel units, so the neighbourhood is an ellipse when axes
+/// are downscaled differently.
+#[derive(Debug, Clone)]
+pub(crate) struct EwaWeights {
+    horizontal: EwaAxis,
+    vertical: EwaAxis,
+    /// Kernel sampled over squared radius
+    lut: Vec<f32>,
+    lut_scale: f32,
+    support_squared: f32,
+    anti_ringing: f32,
+}
+
+impl EwaWeights {
+    /// Prepares weights, filter must be checked by [is_polar_filter] beforehand
+    pub(crate) fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+    ) -> Result<EwaWeights, PicScaleError> {
+        let polar_kernel = polar_kernel(options.resampling_function)
+            .expect("Elliptical weighted averaging is requested for non-polar filter");
+        let blur = options.blur as f64;
+        let taper = options.taper as f64;
+        let support = polar_kernel.radius * blur;
+
+        let mut lut = vec![0f32; LUT_SIZE];
+        let step = support * support / (LUT_SIZE - 1) as f64;
+        for (i, dst) in lut.iter_mut().enumerate() {
+            let x = (i as f64 * step).sqrt() / blur;
+            let flat = taper * polar_kernel.radius;
+            let x = if x <= flat {
+                0.
+            } else {
+                (x - flat) / (1. - taper)
+            };
+            *dst = (polar_kernel.kernel)(x, polar_kernel.radius) as f32;
+        }
+
+        let mappings = options.axis_mappings(destination_size);
+        let horizontal = EwaAxis::new(
+            source_size.width,
+            destination_size.width,
+            mappings.map(|mappings| mappings.0),
+            support,
+            options.edge_mode,
+        )?;
+        let vertical = EwaAxis::new(
+            source_size.height,
+            destination_size.height,
+            mappings.map(|mappings| mappings.1),
+            support,
+            options.edge_mode,
+        )?;
+
+        Ok(EwaWeights {
+            horizontal,
+            vertical,
+            lut,
+            lut_scale: (1. / step) as f32,
+            support_squared: (support * support) as f32,
+            anti_ringing: options.anti_ringing,
+        })
+    }
+
+    #[inline(always)]
+    fn weight(&self, radius_squared: f32) -> f32 {
+        let position = radius_squared * self.lut_scale;
+        let index = (position as usize).min(LUT_SIZE - 2);
+        let fraction = position - index as f32;
+        let lo = self.lut[index];
+        lo + (self.lut[index + 1] - lo) * fraction
+    }
+}
+
+/// Computes one destination pixel
+///
+/// With `premultiply` each tap is weighted by its alpha, which is the last channel,
+/// and colors are un-associated after averaging.
+fn ewa_pixel<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    weights: &EwaWeights,
+    rows: &[EwaTap],
+    columns: &[EwaTap],
+    premultiply: bool,
+) -> [f32; CHANNELS]
+where
+    T: Copy + AsPrimitive<f32>,
+{
+    let mut sums = [0f32; CHANNELS];
+    let mut weights_sum = 0f32;
+    let mut lo = [f32::MAX; CHANNELS];
+    let mut hi = [f32::MIN; CHANNELS];
+    let anti_ringing = weights.anti_ringing > 0.;
+
+    for row_tap in rows.iter() {
+        let dy = row_tap.distance * row_tap.distance;
+        if dy >= weights.support_squared {
+            continue;
+        }
+        let row = row_tap.source.map(|y| &src[y * src_stride..]);
+        for column_tap in columns.iter() {
+            let radius_squared = column_tap.distance * column_tap.distance + dy;
+            if radius_squared >= weights.support_squared {
+                continue;
+            }
+            let weight = weights.weight(radius_squared);
+            weights_sum += weight;
+            let (Some(row), Some(x)) = (row, column_tap.source) else {
+                continue;
+            };
+            let pixel = &row[x * CHANNELS..(x + 1) * CHANNELS];
+            if premultiply {
+                let alpha: f32 = pixel[CHANNELS - 1].as_();
+                let weight = weight * alpha;
+                for (sum, &v) in sums.iter_mut().zip(pixel[..CHANNELS - 1].iter()) {
+                    *sum += v.as_() * weight;
+                }
+                sums[CHANNELS - 1] += weight;
+            } else {
+                for (sum, &v) in sums.iter_mut().zip(pixel.iter()) {
+                    *sum += v.as_() * weight;
+                }
+            }
+            // Taps closer than one kernel unit are bounding anti-ringing clamp
+            if anti_ringing && radius_squared < 1. {
+                for ((lo, hi), &v) in lo.iter_mut().zip(hi.iter_mut()).zip(pixel.iter()) {
+                    let v: f32 = v.as_();
+                    *lo = lo.min(v);
+                    *hi = hi.max(v);
+                }
+            }
+        }
+    }
+
+    let mut pixel = [0f32; CHANNELS];
+    if weights_sum == 0. {
+        return pixel;
+    }
+    let norm = 1. / weights_sum;
+    if premultiply {
+        let alpha = sums[CHANNELS - 1];
+        if alpha > 0. {
+            let norm = 1. / alpha;
+            for (dst, &sum) in pixel.iter_mut().zip(sums[..CHANNELS - 1].iter()) {
+                *dst = sum * norm;
+            }
+        }
+        pixel[CHANNELS - 1] = alpha * norm;
+    } else {
+        for (dst, &sum) in pixel.iter_mut().zip(sums.iter()) {
+            *dst = sum * norm;
+        }
+    }
+    if anti_ringing {
+        for ((v, &lo), &hi) in pixel.iter_mut().zip(lo.iter()).zip(hi.iter()) {
+            if lo <= hi {
+                *v += (v.max(lo).min(hi) - *v) * weights.anti_ringing;
+            }
+        }
+    }
+    pixel
+}
+
+/// Resizes image with elliptical weighted averaging
+///
+/// # Arguments
+///
+/// * `premultiply`: Associate alpha while averaging, alpha is the last channel
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_ewa<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    weights: &EwaWeights,
+    premultiply: bool,
+    executor: &Executor,
+) -> Result<(), PicScaleError>
+where
+    T: Copy + AsPrimitive<f32> + Send + Sync,
+    f32: MixedStorage<T>,
+{
+    let src = check_image_layout::<T, CHANNELS>(src, src_stride, source_size)?;
+    let dst = check_destination_layout::<T, CHANNELS>(dst, dst_stride, destination_size)?;
+
+    let row_length = destination_size.width * CHANNELS;
+
+    let resize_row = |y: usize, dst: &mut [T]| {
+        let rows = weights.vertical.taps(y);
+        for (x, dst) in dst[..row_length].chunks_exact_mut(CHANNELS).enumerate() {
+            let columns = weights.horizontal.taps(x);
+            let pixel =
+                ewa_pixel::<T, CHANNELS>(src, src_stride, weights, rows, columns, premultiply);
+            for (dst, v) in dst.iter_mut().zip(pixel) {
+                *dst = v.to_mixed(bit_depth);
+            }
+        }
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            dst.par_chunks_mut(dst_stride)
+                .with_min_len(executor.min_len(destination_size.width))
+                .enumerate()
+                .for_each(|(y, dst)| resize_row(y, dst));
+        });
+    } else {
+        for (y, dst) in dst.chunks_mut(dst_stride).enumerate() {
+            resize_row(y, dst);
+        }
+    }
+    Ok(())
+}
@@ -26,6 +26,8 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::anti_ringing::AntiRinging;
+use crate::edge_mode::EdgeMode;
 use num_traits::{AsPrimitive, Float};
 
 #[derive(Debug, Clone)]
@@ -36,6 +38,11 @@ pub(crate) struct FilterWeights<T> {
     pub aligned_size: usize,
     pub distinct_elements: usize,
     pub coeffs_size: i32,
+    /// Bounds are in coordinates of the image padded by this amount at both sides
+    pub padding: usize,
+    pub edge_mode: EdgeMode,
+    /// Clamp to the nearest taps, `None` when disabled
+    pub anti_ringing: Option<AntiRinging>,
 }
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -66,6 +73,24 @@ impl<T> FilterWeights<T> {
             aligned_size,
             distinct_elements,
             coeffs_size,
+            padding: 0,
+            edge_mode: EdgeMode::Shrink,
+            anti_ringing: None,
+        }
+    }
+
+    pub(crate) fn with_edge_mode(self, edge_mode: EdgeMode, padding: usize) -> FilterWeights<T> {
+        FilterWeights::<T> {
+            padding,
+            edge_mode,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_anti_ringing(self, anti_ringing: Option<AntiRinging>) -> FilterWeights<T> {
+        FilterWeights::<T> {
+            anti_ringing,
+            ..self
         }
     }
 }
@@ -113,5 +138,7 @@ where
             self.coeffs_size,
             new_bounds,
         )
+        .with_edge_mode(self.edge_mode, self.padding)
+        .with_anti_ringing(self.anti_ringing.clone())
     }
 }
@@ -54,6 +54,11 @@ pub(crate) fn convolve_row_handler_fixed_point<
     i32: AsPrimitive<J>,
     i16: AsPrimitive<J>,
 {
+    if CHANNELS > 4 {
+        convolve_row_handler_fixed_point_n::<T, J, CHANNELS>(src, dst, filter_weights, bit_depth);
+        return;
+    }
+
     for ((chunk, &bounds), weights) in dst
         .chunks_exact_mut(CHANNELS)
         .zip(filter_weights.bounds.iter())
@@ -108,6 +113,18 @@ pub(crate) fn convolve_row_handler_fixed_point_4<
     i32: AsPrimitive<J>,
     i16: AsPrimitive<J>,
 {
+    if CHANNELS > 4 {
+        for (src, dst) in src.chunks(src_stride).zip(dst.chunks_mut(dst_stride)) {
+            convolve_row_handler_fixed_point_n::<T, J, CHANNELS>(
+                src,
+                dst,
+                filter_weights,
+                bit_depth,
+            );
+        }
+        return;
+    }
+
     let (row0_ref, rest) = dst.split_at_mut(dst_stride);
     let (row1_ref, rest) = rest.split_at_mut(dst_stride);
     let (row2_ref, row3_ref) = rest.split_at_mut(dst_stride);
@@ -174,3 +191,49 @@ pub(crate) fn convolve_row_handler_fixed_point_4<
         store_color_group!(narrowed3, chunk3, CHANNELS);
     }
 }
+
+/// Handles pixels with more than 4 channels, [ColorGroup] holds at most 4 of them
+#[inline(always)]
+fn convolve_row_handler_fixed_point_n<
+    T: Copy + 'static + AsPrimitive<J> + Default,
+    J: Copy + 'static + AsPrimitive<T> + Mul<Output = J> + AddAssign + SaturateNarrow<T> + Default,
+    const CHANNELS: usize,
+>(
+    src: &[T],
+    dst: &mut [T],
+    filter_weights: &FilterWeights<i16>,
+    bit_depth: u32,
+) where
+    i32: AsPrimitive<J>,
+    i16: AsPrimitive<J>,
+{
+    for ((chunk, &bounds), weights) in dst
+        .chunks_exact_mut(CHANNELS)
+        .zip(filter_weights.bounds.iter())
+        .zip(
+            filter_weights
+                .weights
+                .chunks_exact(filter_weights.aligned_size),
+        )
+    {
+        let mut sums: [J; CHANNELS] = [ROUNDING_CONST.as_(); CHANNELS];
+
+        let px = bounds.start * CHANNELS;
+
+        let src_ptr0 = &src[px..(px + bounds.size * CHANNELS)];
+        for (&k_weight, src) in weights[..bounds.size]
+            .iter()
+            .zip(src_ptr0.chunks_exact(CHANNELS))
+        {
+            let weight: J = k_weight.as_();
+            for (sum, &v) in sums.iter_mut().zip(src.iter()) {
+                let v: J = v.as_();
+                *sum += v * weight;
+            }
+        }
+
+        for (dst, sum) in chunk.iter_mut().zip(sums.iter()) {
+            *dst = sum.saturate_narrow(bit_depth);
+        }
+    }
+}
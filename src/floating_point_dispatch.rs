@@ -27,101 +27,164 @@
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+use crate::anti_ringing::{anti_ring_column, anti_ring_row, anti_ring_rows};
+use crate::column_tiles::ColumnTiles;
+use crate::edge_mode::{column_source, convolve_padded_rows};
+use crate::error::PicScaleError;
 use crate::filter_weights::FilterWeights;
 use crate::handler_provider::{ColumnHandlerFloatingPoint, RowHandlerFloatingPoint};
 use crate::image_size::ImageSize;
 use crate::mixed_storage::MixedStorage;
+use crate::threading::Executor;
+use crate::validation::{check_destination_layout, check_image_layout};
 use num_traits::{AsPrimitive, MulAdd};
 #[cfg(feature = "rayon")]
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 #[cfg(feature = "rayon")]
 use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+use std::ops::Range;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn convolve_row_floating_point<T, J, F, const CHANNELS: usize>(
     image_store: &[T],
+    src_stride: usize,
     image_size: ImageSize,
-    weights: FilterWeights<F>,
+    weights: &FilterWeights<F>,
     destination: &mut [T],
+    dst_stride: usize,
     destination_size: ImageSize,
     bit_depth: u32,
-) where
+    executor: &Executor,
+) -> Result<(), PicScaleError>
+where
     T: Copy + 'static + AsPrimitive<J> + Default + RowHandlerFloatingPoint<T, J, F> + Sync + Send,
     J: Copy + 'static + AsPrimitive<T> + MulAdd<J, Output = J> + Default + MixedStorage<T>,
     F: Copy + 'static + AsPrimitive<J> + Send + Sync,
     i32: AsPrimitive<J>,
     f32: AsPrimitive<J>,
 {
-    assert_eq!(
-        image_store.len(),
-        image_size.width * image_size.height * CHANNELS,
-        "Source image slice must match its dimensions"
-    );
-    assert_eq!(
-        destination.len(),
-        destination_size.width * destination_size.height * CHANNELS,
-        "Source image slice must match its dimensions"
-    );
-
-    let (src_stride, k_overflowed) = image_size.width.overflowing_mul(CHANNELS);
-    assert!(!k_overflowed, "Stride must be always less than usize::MAX");
-    let src_stride_4 = src_stride * 4;
+    let image_store = check_image_layout::<T, CHANNELS>(image_store, src_stride, image_size)?;
+    let destination =
+        check_destination_layout::<T, CHANNELS>(destination, dst_stride, destination_size)?;
+
+    if weights.padding != 0 {
+        convolve_padded_rows::<T, CHANNELS>(
+            image_store,
+            src_stride,
+            image_size.width,
+            destination,
+            dst_stride,
+            weights.padding,
+            weights.edge_mode,
+            |src, dst| {
+                T::handle_row::<CHANNELS>(src, dst, weights, bit_depth);
+                if let Some(anti_ringing) = &weights.anti_ringing {
+                    anti_ring_row::<T, CHANNELS>(src, dst, &weights.bounds, anti_ringing);
+                }
+            },
+            executor,
+        );
+        return Ok(());
+    }
 
-    let (dst_stride, k_overflowed) = destination_size.width.overflowing_mul(CHANNELS);
-    assert!(!k_overflowed, "Stride must be always less than usize::MAX");
+    let src_stride_4 = src_stride * 4;
     let dst_stride_4 = dst_stride * 4;
 
-    #[cfg(not(feature = "rayon"))]
-    {
-        let image_store_4_iter = image_store.chunks_exact(src_stride_4);
-        let dst_store_4_iter = destination.chunks_exact_mut(dst_stride_4);
+    // Last row might have no padding, so images are split by rows count rather than by length
+    let rows_4 = image_size.height / 4 * 4;
+    let (image_store_4, image_store_rem) =
+        image_store.split_at((rows_4 * src_stride).min(image_store.len()));
+    let (destination_4, destination_rem) =
+        destination.split_at_mut((rows_4 * dst_stride).min(destination.len()));
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            let image_store_4_iter = image_store_4.par_chunks(src_stride_4);
+            let dst_store_4_iter = destination_4.par_chunks_mut(dst_stride_4);
+
+            image_store_4_iter
+                .zip(dst_store_4_iter)
+                .with_min_len(executor.min_len(4 * destination_size.width))
+                .for_each(|(src, dst)| {
+                    T::handle_row_4::<CHANNELS>(
+                        src, src_stride, dst, dst_stride, weights, bit_depth,
+                    );
+                    anti_ring_rows::<T, CHANNELS>(
+                        src,
+                        src_stride,
+                        dst,
+                        dst_stride,
+                        &weights.bounds,
+                        weights.anti_ringing.as_ref(),
+                    );
+                });
+
+            let image_store_iter = image_store_rem.par_chunks(src_stride);
+            let dst_store_iter = destination_rem.par_chunks_mut(dst_stride);
+
+            image_store_iter
+                .zip(dst_store_iter)
+                .with_min_len(executor.min_len(destination_size.width))
+                .for_each(|(src, dst)| {
+                    T::handle_row::<CHANNELS>(src, dst, weights, bit_depth);
+                    anti_ring_rows::<T, CHANNELS>(
+                        src,
+                        src_stride,
+                        dst,
+                        dst_stride,
+                        &weights.bounds,
+                        weights.anti_ringing.as_ref(),
+                    );
+                });
+        });
+    } else {
+        let image_store_4_iter = image_store_4.chunks(src_stride_4);
+        let dst_store_4_iter = destination_4.chunks_mut(dst_stride_4);
 
         for (src, dst) in image_store_4_iter.zip(dst_store_4_iter) {
-            T::handle_row_4::<CHANNELS>(src, src_stride, dst, dst_stride, &weights, bit_depth);
+            T::handle_row_4::<CHANNELS>(src, src_stride, dst, dst_stride, weights, bit_depth);
+            anti_ring_rows::<T, CHANNELS>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                &weights.bounds,
+                weights.anti_ringing.as_ref(),
+            );
         }
 
-        let image_store_iter_rem = image_store.chunks_exact(src_stride_4).remainder();
-        let dst_store_iter_rem = destination.chunks_exact_mut(dst_stride_4).into_remainder();
-
-        let image_store_iter = image_store_iter_rem.chunks_exact(src_stride);
-        let dst_store_iter = dst_store_iter_rem.chunks_exact_mut(dst_stride);
+        let image_store_iter = image_store_rem.chunks(src_stride);
+        let dst_store_iter = destination_rem.chunks_mut(dst_stride);
 
         for (src, dst) in image_store_iter.zip(dst_store_iter) {
-            T::handle_row::<CHANNELS>(src, dst, &weights, bit_depth);
+            T::handle_row::<CHANNELS>(src, dst, weights, bit_depth);
+            anti_ring_rows::<T, CHANNELS>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                &weights.bounds,
+                weights.anti_ringing.as_ref(),
+            );
         }
     }
-    #[cfg(feature = "rayon")]
-    {
-        let image_store_4_iter = image_store.par_chunks_exact(src_stride_4);
-        let dst_store_4_iter = destination.par_chunks_exact_mut(dst_stride_4);
-
-        image_store_4_iter
-            .zip(dst_store_4_iter)
-            .for_each(|(src, dst)| {
-                T::handle_row_4::<CHANNELS>(src, src_stride, dst, dst_stride, &weights, bit_depth);
-            });
-
-        let image_store_iter_rem = image_store.par_chunks_exact(src_stride_4).remainder();
-        let dst_store_iter_rem = destination
-            .par_chunks_exact_mut(dst_stride_4)
-            .into_remainder();
-
-        let image_store_iter = image_store_iter_rem.par_chunks_exact(src_stride);
-        let dst_store_iter = dst_store_iter_rem.par_chunks_exact_mut(dst_stride);
-
-        image_store_iter.zip(dst_store_iter).for_each(|(src, dst)| {
-            T::handle_row::<CHANNELS>(src, dst, &weights, bit_depth);
-        });
-    }
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn convolve_column_floating_point<T, J, F, const CHANNELS: usize>(
     image_store: &[T],
+    src_stride: usize,
     image_size: ImageSize,
-    weights: FilterWeights<F>,
+    filter_weights: &FilterWeights<F>,
     destination: &mut [T],
+    dst_stride: usize,
     destination_size: ImageSize,
     bit_depth: u32,
-) where
+    executor: &Executor,
+) -> Result<(), PicScaleError>
+where
     T: Copy
         + 'static
         + AsPrimitive<J>
@@ -134,40 +197,82 @@ pub(crate) fn convolve_column_floating_point<T, J, F, const CHANNELS: usize>(
     i32: AsPrimitive<J>,
     f32: AsPrimitive<J>,
 {
-    assert_eq!(
-        image_store.len(),
-        image_size.width * image_size.height * CHANNELS,
-        "Source image slice must match its dimensions"
-    );
-    assert_eq!(
-        destination.len(),
-        destination_size.width * destination_size.height * CHANNELS,
-        "Source image slice must match its dimensions"
-    );
-
-    let (src_stride, k_overflowed) = image_size.width.overflowing_mul(CHANNELS);
-    assert!(!k_overflowed, "Stride must be always less than usize::MAX");
-    let (dst_stride, k_overflowed) = destination_size.width.overflowing_mul(CHANNELS);
-    assert!(!k_overflowed, "Stride must be always less than usize::MAX");
-
-    #[cfg(feature = "rayon")]
-    {
-        let dst_store_iter = destination.par_chunks_exact_mut(dst_stride);
-        dst_store_iter
-            .zip(weights.bounds.par_iter())
-            .zip(weights.weights.par_chunks_exact(weights.aligned_size))
-            .for_each(|((dst, bounds), weights)| {
-                T::handle_column(bounds, image_store, dst, src_stride, weights, bit_depth);
-            });
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        let dst_store_iter = destination.chunks_exact_mut(dst_stride);
-        for ((dst, bounds), weights) in dst_store_iter
-            .zip(weights.bounds)
-            .zip(weights.weights.chunks_exact(weights.aligned_size))
-        {
-            T::handle_column(&bounds, image_store, dst, src_stride, weights, bit_depth);
+    let image_store = check_image_layout::<T, CHANNELS>(image_store, src_stride, image_size)?;
+    let destination =
+        check_destination_layout::<T, CHANNELS>(destination, dst_stride, destination_size)?;
+
+    let row_length = destination_size.width * CHANNELS;
+    let tiles = ColumnTiles::new::<T>(row_length, filter_weights.kernel_size);
+
+    let column_pass =
+        |y: usize, dst: &mut [T], bounds, weights, rows: &mut Vec<T>, strip: Range<usize>| {
+            let (bounds, src, src_stride) = column_source(
+                bounds,
+                &image_store[strip.start..],
+                src_stride,
+                strip.len(),
+                image_size.height,
+                filter_weights.padding,
+                filter_weights.edge_mode,
+                rows,
+            );
+            let dst = &mut dst[strip];
+            T::handle_column(&bounds, src, dst, src_stride, weights, bit_depth);
+            if let Some(anti_ringing) = &filter_weights.anti_ringing {
+                anti_ring_column(
+                    &bounds,
+                    &anti_ringing.taps[y],
+                    src,
+                    src_stride,
+                    dst,
+                    anti_ringing.strength,
+                );
+            }
+        };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            let band_height = tiles.band_height(destination_size.height);
+            destination
+                .par_chunks_mut(dst_stride * band_height)
+                .with_min_len(executor.min_len(band_height * destination_size.width))
+                .enumerate()
+                .for_each_init(Vec::new, |rows, (band, dst)| {
+                    let first_row = band * band_height;
+                    for strip in tiles.strips() {
+                        for (j, dst) in dst.chunks_mut(dst_stride).enumerate() {
+                            let y = first_row + j;
+                            let weights = &filter_weights.weights[y * filter_weights.aligned_size
+                                ..(y + 1) * filter_weights.aligned_size];
+                            column_pass(
+                                y,
+                                dst,
+                                &filter_weights.bounds[y],
+                                weights,
+                                rows,
+                                strip.clone(),
+                            );
+                        }
+                    }
+                });
+        });
+    } else {
+        let mut rows = Vec::new();
+        for strip in tiles.strips() {
+            let dst_store_iter = destination.chunks_mut(dst_stride);
+            for (((y, dst), bounds), weights) in dst_store_iter
+                .enumerate()
+                .zip(filter_weights.bounds.iter())
+                .zip(
+                    filter_weights
+                        .weights
+                        .chunks_exact(filter_weights.aligned_size),
+                )
+            {
+                column_pass(y, dst, bounds, weights, &mut rows, strip.clone());
+            }
         }
     }
+    Ok(())
 }
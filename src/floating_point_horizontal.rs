@@ -29,6 +29,7 @@
 use crate::color_group::{fast_mixed_store_color_group, load_color_group, ColorGroup};
 use crate::filter_weights::FilterWeights;
 use crate::mixed_storage::MixedStorage;
+use crate::mlaf::mlaf;
 use num_traits::{AsPrimitive, Float, MulAdd};
 use std::ops::{Add, Mul};
 
@@ -57,6 +58,16 @@ pub(crate) fn convolve_row_handler_floating_point<
 ) where
     i32: AsPrimitive<J>,
 {
+    if CHANNELS > 4 {
+        convolve_row_handler_floating_point_n::<T, J, F, CHANNELS>(
+            src,
+            dst,
+            filter_weights,
+            bit_depth,
+        );
+        return;
+    }
+
     for ((chunk, &bounds), weights) in dst
         .chunks_exact_mut(CHANNELS)
         .zip(filter_weights.bounds.iter())
@@ -113,6 +124,18 @@ pub(crate) fn convolve_row_handler_floating_point_4<
 ) where
     i32: AsPrimitive<J>,
 {
+    if CHANNELS > 4 {
+        for (src, dst) in src.chunks(src_stride).zip(dst.chunks_mut(dst_stride)) {
+            convolve_row_handler_floating_point_n::<T, J, F, CHANNELS>(
+                src,
+                dst,
+                filter_weights,
+                bit_depth,
+            );
+        }
+        return;
+    }
+
     let (row0_ref, rest) = dst.split_at_mut(dst_stride);
     let (row1_ref, rest) = rest.split_at_mut(dst_stride);
     let (row2_ref, row3_ref) = rest.split_at_mut(dst_stride);
@@ -172,3 +195,55 @@ pub(crate) fn convolve_row_handler_floating_point_4<
         fast_mixed_store_color_group!(sums3, chunk3, CHANNELS, bit_depth);
     }
 }
+
+#[inline(always)]
+/// Handles pixels with more than 4 channels, [ColorGroup] holds at most 4 of them
+fn convolve_row_handler_floating_point_n<
+    T: Copy + 'static + AsPrimitive<J> + Default,
+    J: Copy
+        + 'static
+        + AsPrimitive<T>
+        + MulAdd<J, Output = J>
+        + Mul<J, Output = J>
+        + Add<J, Output = J>
+        + Default
+        + MixedStorage<T>,
+    F: Copy + 'static + Float + AsPrimitive<J>,
+    const CHANNELS: usize,
+>(
+    src: &[T],
+    dst: &mut [T],
+    filter_weights: &FilterWeights<F>,
+    bit_depth: u32,
+) where
+    i32: AsPrimitive<J>,
+{
+    for ((chunk, &bounds), weights) in dst
+        .chunks_exact_mut(CHANNELS)
+        .zip(filter_weights.bounds.iter())
+        .zip(
+            filter_weights
+                .weights
+                .chunks_exact(filter_weights.aligned_size),
+        )
+    {
+        let mut sums: [J; CHANNELS] = [0.as_(); CHANNELS];
+
+        let px = bounds.start * CHANNELS;
+
+        let src_ptr0 = &src[px..(px + bounds.size * CHANNELS)];
+        for (&k_weight, src) in weights[..bounds.size]
+            .iter()
+            .zip(src_ptr0.chunks_exact(CHANNELS))
+        {
+            let weight: J = k_weight.as_();
+            for (sum, &v) in sums.iter_mut().zip(src.iter()) {
+                *sum = mlaf(*sum, v.as_(), weight);
+            }
+        }
+
+        for (dst, &sum) in chunk.iter_mut().zip(sums.iter()) {
+            *dst = sum.to_mixed(bit_depth);
+        }
+    }
+}
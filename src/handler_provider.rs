@@ -26,6 +26,7 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::anti_ringing::AntiRingingSample;
 use crate::filter_weights::{FilterBounds, FilterWeights};
 use crate::fixed_point_horizontal::{
     convolve_row_handler_fixed_point, convolve_row_handler_fixed_point_4,
@@ -40,7 +41,7 @@ use crate::saturate_narrow::SaturateNarrow;
 use num_traits::{AsPrimitive, Float, MulAdd};
 use std::ops::{Add, AddAssign, Mul};
 
-pub(crate) trait ColumnHandlerFixedPoint<T, J>
+pub(crate) trait ColumnHandlerFixedPoint<T, J>: AntiRingingSample
 where
     T: Copy + 'static + AsPrimitive<J> + Default,
     J: Copy + 'static + AsPrimitive<T> + Mul<Output = J> + AddAssign + SaturateNarrow<T> + Default,
@@ -57,7 +58,7 @@ where
     );
 }
 
-pub(crate) trait RowHandlerFixedPoint<T, J>
+pub(crate) trait RowHandlerFixedPoint<T, J>: AntiRingingSample
 where
     T: Copy + 'static + AsPrimitive<J> + Default,
     J: Copy + 'static + AsPrimitive<T> + Mul<Output = J> + AddAssign + SaturateNarrow<T> + Default,
@@ -215,7 +216,7 @@ where
     }
 }
 
-pub(crate) trait ColumnHandlerFloatingPoint<T, J, F>
+pub(crate) trait ColumnHandlerFloatingPoint<T, J, F>: AntiRingingSample
 where
     T: Copy + 'static + AsPrimitive<J> + Default,
     J: Copy + 'static + AsPrimitive<T> + MulAdd<J, Output = J> + Default + MixedStorage<T>,
@@ -271,8 +272,10 @@ default_floating_column_handler!(u16);
 default_floating_column_handler!(u32);
 default_floating_column_handler!(f32);
 default_floating_column_handler!(f64);
+#[cfg(feature = "half")]
+default_floating_column_handler!(half::f16);
 
-pub(crate) trait RowHandlerFloatingPoint<T, J, F>
+pub(crate) trait RowHandlerFloatingPoint<T, J, F>: AntiRingingSample
 where
     T: Copy + 'static + AsPrimitive<J> + Default,
     J: Copy + 'static + AsPrimitive<T> + MulAdd<J, Output = J> + Default + MixedStorage<T>,
@@ -353,3 +356,5 @@ default_floating_column_handler!(f32);
 default_floating_column_handler!(f64);
 default_floating_column_handler!(u8);
 default_floating_column_handler!(u16);
+#[cfg(feature = "half")]
+default_floating_column_handler!(half::f16);
@@ -0,0 +1,252 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::image_size::ImageSize;
+use crate::threading::Executor;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::prelude::ParallelSliceMut;
+use std::ops::AddAssign;
+
+/// Resize where sizes along both axes are in exact integral ratio
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum IntegerRatio {
+    /// Downscale, destination pixel is the mean of `x_factor * y_factor` block of the source
+    Average { x_factor: usize, y_factor: usize },
+    /// Upscale, source pixel is repeated over `x_factor * y_factor` block of the destination
+    Replicate { x_factor: usize, y_factor: usize },
+}
+
+impl IntegerRatio {
+    /// Factors of `large` over `small` along both axes, `None` when either does not divide evenly
+    pub(crate) fn factors(large: ImageSize, small: ImageSize) -> Option<(usize, usize)> {
+        let remainders = (large.width % small.width, large.height % small.height);
+        if remainders != (0, 0) {
+            return None;
+        }
+        Some((large.width / small.width, large.height / small.height))
+    }
+}
+
+/// Sample type which might be averaged over a block
+pub(crate) trait BlockAverage: Copy {
+    /// Accumulator wide enough to hold sum of any block
+    type Sum: Copy + Default + AddAssign + Send;
+
+    fn widen(self) -> Self::Sum;
+
+    /// Mean of `count` samples, rounded to the nearest for integral types
+    fn average(sum: Self::Sum, count: usize) -> Self;
+}
+
+macro_rules! impl_integral_block_average {
+    ($ty:ty) => {
+        impl BlockAverage for $ty {
+            type Sum = u64;
+
+            #[inline(always)]
+            fn widen(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn average(sum: u64, count: usize) -> $ty {
+                let count = count as u64;
+                ((sum + count / 2) / count) as $ty
+            }
+        }
+    };
+}
+
+impl_integral_block_average!(u8);
+impl_integral_block_average!(u16);
+
+impl BlockAverage for f32 {
+    type Sum = f32;
+
+    #[inline(always)]
+    fn widen(self) -> f32 {
+        self
+    }
+
+    #[inline(always)]
+    fn average(sum: f32, count: usize) -> f32 {
+        sum / count as f32
+    }
+}
+
+#[cfg(feature = "half")]
+impl BlockAverage for half::f16 {
+    type Sum = f32;
+
+    #[inline(always)]
+    fn widen(self) -> f32 {
+        self.to_f32()
+    }
+
+    #[inline(always)]
+    fn average(sum: f32, count: usize) -> half::f16 {
+        half::f16::from_f32(sum / count as f32)
+    }
+}
+
+/// Executes integral ratio resize, layouts must be validated by the caller
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_integer_ratio<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    ratio: IntegerRatio,
+    executor: &Executor,
+) where
+    T: BlockAverage + Send + Sync,
+{
+    match ratio {
+        IntegerRatio::Average { x_factor, y_factor } => average_blocks::<T, CHANNELS>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            destination_size,
+            x_factor,
+            y_factor,
+            executor,
+        ),
+        IntegerRatio::Replicate { x_factor, y_factor } => replicate_pixels::<T, CHANNELS>(
+            src,
+            src_stride,
+            dst,
+            dst_stride,
+            destination_size,
+            x_factor,
+            y_factor,
+            executor,
+        ),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn average_blocks<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    x_factor: usize,
+    y_factor: usize,
+    executor: &Executor,
+) where
+    T: BlockAverage + Send + Sync,
+{
+    let dst_row_length = destination_size.width * CHANNELS;
+    let block_length = x_factor * CHANNELS;
+    let count = x_factor * y_factor;
+
+    let average_row = |y: usize, dst_row: &mut [T], sums: &mut Vec<T::Sum>| {
+        sums.clear();
+        sums.resize(dst_row_length, T::Sum::default());
+        for src_row in src[y * y_factor * src_stride..]
+            .chunks(src_stride)
+            .take(y_factor)
+        {
+            for (sum, block) in sums
+                .chunks_exact_mut(CHANNELS)
+                .zip(src_row.chunks_exact(block_length))
+            {
+                for px in block.chunks_exact(CHANNELS) {
+                    for (sum, &v) in sum.iter_mut().zip(px.iter()) {
+                        *sum += v.widen();
+                    }
+                }
+            }
+        }
+        for (dst, &sum) in dst_row[..dst_row_length].iter_mut().zip(sums.iter()) {
+            *dst = T::average(sum, count);
+        }
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            dst.par_chunks_mut(dst_stride)
+                .with_min_len(executor.min_len(destination_size.width * count))
+                .enumerate()
+                .for_each_init(Vec::new, |sums, (y, dst_row)| average_row(y, dst_row, sums));
+        });
+    } else {
+        let mut sums = Vec::new();
+        for (y, dst_row) in dst.chunks_mut(dst_stride).enumerate() {
+            average_row(y, dst_row, &mut sums);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn replicate_pixels<T, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    x_factor: usize,
+    y_factor: usize,
+    executor: &Executor,
+) where
+    T: Copy + Send + Sync,
+{
+    let dst_row_length = destination_size.width * CHANNELS;
+
+    let replicate_row = |y: usize, dst_row: &mut [T]| {
+        let src_row = &src[(y / y_factor) * src_stride..];
+        for (block, px) in dst_row[..dst_row_length]
+            .chunks_exact_mut(x_factor * CHANNELS)
+            .zip(src_row.chunks_exact(CHANNELS))
+        {
+            for dst_px in block.chunks_exact_mut(CHANNELS) {
+                dst_px.copy_from_slice(px);
+            }
+        }
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            dst.par_chunks_mut(dst_stride)
+                .with_min_len(executor.min_len(destination_size.width))
+                .enumerate()
+                .for_each(|(y, dst_row)| replicate_row(y, dst_row));
+        });
+    } else {
+        for (y, dst_row) in dst.chunks_mut(dst_stride).enumerate() {
+            replicate_row(y, dst_row);
+        }
+    }
+}
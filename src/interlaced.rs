@@ -0,0 +1,332 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::image_size::ImageSize;
+use crate::resize_options::ResizeOptions;
+use crate::resize_plan::{ChannelLayout, ResizePlan};
+use crate::sampler::ResamplingFunction;
+use crate::scale_transform::ScaleTransform;
+use crate::validation::{check_image_layout, check_layout, packed_stride};
+use crate::PicScaleError;
+
+/// Field of interlaced frame
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+enum Field {
+    /// Even rows of the frame
+    Top,
+    /// Odd rows of the frame
+    Bottom,
+}
+
+impl Field {
+    /// Size of the field in the frame of `frame_size`
+    fn size(self, frame_size: ImageSize) -> ImageSize {
+        let height = match self {
+            Field::Top => frame_size.height.div_ceil(2),
+            Field::Bottom => frame_size.height / 2,
+        };
+        ImageSize::new(frame_size.width, height)
+    }
+
+    /// Options of the field, destination field is placed over the source one
+    ///
+    /// Field row `i` lays at frame row `2 * i` for top field and `2 * i + 1` for bottom field,
+    /// so when frame is scaled by `ratio` rows of destination field are found in source field
+    /// at `(ratio - 1) / 4` lines above the center for top field and below it for bottom field.
+    fn options(
+        self,
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: ResizeOptions,
+    ) -> ResizeOptions {
+        if source_size.height == destination_size.height {
+            return options;
+        }
+        let ratio = source_size.height as f64 / destination_size.height as f64;
+        let offset = match self {
+            Field::Top => -(ratio - 1.) / 4.,
+            Field::Bottom => (ratio - 1.) / 4.,
+        };
+        options.with_scale_transform(ScaleTransform::new(
+            (destination_size.width as f64 / source_size.width as f64) as f32,
+            (1. / ratio) as f32,
+            0.,
+            offset as f32,
+        ))
+    }
+
+    /// Copies rows of the field out of the frame
+    fn extract<T: Copy>(self, frame: &[T], frame_size: ImageSize) -> Vec<T> {
+        let skip = match self {
+            Field::Top => 0,
+            Field::Bottom => 1,
+        };
+        frame
+            .chunks_exact(frame_size.width)
+            .skip(skip)
+            .step_by(2)
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// Interleaves rows of top and bottom fields into the frame
+fn weave<T: Copy>(top: &[T], bottom: &[T], frame_size: ImageSize) -> Vec<T> {
+    let width = frame_size.width;
+    let mut frame = Vec::with_capacity(width * frame_size.height);
+    let mut top_rows = top.chunks_exact(width);
+    let mut bottom_rows = bottom.chunks_exact(width);
+    for y in 0..frame_size.height {
+        let row = if y % 2 == 0 {
+            top_rows.next()
+        } else {
+            bottom_rows.next()
+        };
+        if let Some(row) = row {
+            frame.extend_from_slice(row);
+        }
+    }
+    frame
+}
+
+/// Resizes both fields of the frame, creating plans with `make_plan`
+fn resize_fields<T: Copy + Default>(
+    source: &[T],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    make_plan: impl Fn(ImageSize, ImageSize, ResizeOptions) -> Result<ResizePlan<T>, PicScaleError>,
+    execute: impl Fn(&ResizePlan<T>, &[T], &mut [T], &mut Vec<T>) -> Result<(), PicScaleError>,
+) -> Result<(Vec<T>, Vec<T>), PicScaleError> {
+    let source = check_image_layout::<T, 1>(source, packed_stride::<1>(source_size)?, source_size)?;
+    check_layout::<1>(packed_stride::<1>(destination_size)?, destination_size)?;
+    // Each field must have at least one row
+    for size in [source_size, destination_size] {
+        if size.height < 2 {
+            return Err(PicScaleError::InterlacedTooShort(size.height));
+        }
+    }
+    let options = ResizeOptions::new(resampling_function);
+    let mut scratch = Vec::new();
+    let mut resize_field = |field: Field| {
+        let plan = make_plan(
+            field.size(source_size),
+            field.size(destination_size),
+            field.options(source_size, destination_size, options),
+        )?;
+        let destination_field = field.size(destination_size);
+        let mut destination =
+            vec![T::default(); destination_field.width * destination_field.height];
+        execute(
+            &plan,
+            &field.extract(source, source_size),
+            &mut destination,
+            &mut scratch,
+        )?;
+        Ok(destination)
+    };
+    let top = resize_field(Field::Top)?;
+    let bottom = resize_field(Field::Bottom)?;
+    Ok((top, bottom))
+}
+
+/// Performs field-aware resizing on interlaced planar 8 bit-depth image
+///
+/// Top and bottom fields are resized independently, with vertical weights shifted
+/// by a quarter of the field line according to field position, and then re-woven,
+/// so fields are never mixed and no combing appears.
+///
+/// # Arguments
+///
+/// * `source`: Source frame with woven fields, top field is the first row
+/// * `source_size`: Source image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `destination_size`: Destination image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized frame with woven fields, this bounds always match destination size
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{resize_plane8_interlaced, ImageSize, ResamplingFunction};
+///
+/// let frame = vec![16u8; 720 * 576];
+/// let resized = resize_plane8_interlaced(
+///     &frame,
+///     ImageSize::new(720, 576),
+///     ImageSize::new(1920, 1080),
+///     ResamplingFunction::Lanczos3,
+/// )
+/// .unwrap();
+/// assert_eq!(resized.len(), 1920 * 1080);
+/// ```
+///
+/// # Limitations
+///
+/// This is using integral approximations, same as [crate::resize_plane8] does.
+///
+pub fn resize_plane8_interlaced(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    let (top, bottom) =
+        resize_plane8_fields(source, source_size, destination_size, resampling_function)?;
+    Ok(weave(&top, &bottom, destination_size))
+}
+
+/// Performs field-aware resizing on interlaced planar 8 bit-depth image, keeping fields apart
+///
+/// Same as [resize_plane8_interlaced], but fields are not re-woven.
+///
+/// # Arguments
+///
+/// * `source`: Source frame with woven fields, top field is the first row
+/// * `source_size`: Source image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `destination_size`: Destination image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Top field of `destination_size.height.div_ceil(2)` rows
+/// and bottom field of `destination_size.height / 2` rows
+///
+pub fn resize_plane8_fields(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<(Vec<u8>, Vec<u8>), PicScaleError> {
+    resize_fields(
+        source,
+        source_size,
+        destination_size,
+        resampling_function,
+        |source_size, destination_size, options| {
+            ResizePlan::<u8>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                options,
+            )
+        },
+        |plan, source, destination, scratch| plan.resize_with_scratch(source, destination, scratch),
+    )
+}
+
+/// Performs field-aware resizing on interlaced planar 8-16 bit-depth image
+///
+/// Same as [resize_plane8_interlaced], but for high bit-depth images.
+///
+/// # Arguments
+///
+/// * `source`: Source frame with woven fields, top field is the first row
+/// * `source_size`: Source image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `destination_size`: Destination image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `bit_depth`: Bit-depth of the image, must be in range 1..=16
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized frame with woven fields, this bounds always match destination size
+///
+/// # Limitations
+///
+/// This is using integral approximations for images 10, 12 bit depth,
+/// same as [crate::resize_plane16] does.
+///
+pub fn resize_plane16_interlaced(
+    source: &[u16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u16>, PicScaleError> {
+    let (top, bottom) = resize_plane16_fields(
+        source,
+        source_size,
+        destination_size,
+        bit_depth,
+        resampling_function,
+    )?;
+    Ok(weave(&top, &bottom, destination_size))
+}
+
+/// Performs field-aware resizing on interlaced planar 8-16 bit-depth image, keeping fields apart
+///
+/// Same as [resize_plane16_interlaced], but fields are not re-woven.
+///
+/// # Arguments
+///
+/// * `source`: Source frame with woven fields, top field is the first row
+/// * `source_size`: Source image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `destination_size`: Destination image size, height must be at least 2,
+///   otherwise [PicScaleError::InterlacedTooShort] is returned
+/// * `bit_depth`: Bit-depth of the image, must be in range 1..=16
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Top field of `destination_size.height.div_ceil(2)` rows
+/// and bottom field of `destination_size.height / 2` rows
+///
+pub fn resize_plane16_fields(
+    source: &[u16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+) -> Result<(Vec<u16>, Vec<u16>), PicScaleError> {
+    resize_fields(
+        source,
+        source_size,
+        destination_size,
+        resampling_function,
+        |source_size, destination_size, options| {
+            ResizePlan::<u16>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                bit_depth,
+                options,
+            )
+        },
+        |plan, source, destination, scratch| plan.resize_with_scratch(source, destination, scratch),
+    )
+}
@@ -33,9 +33,16 @@
 
 mod alpha;
 mod alpha_check;
+mod alpha_transform;
+mod anti_ringing;
 mod color_group;
+mod column_tiles;
 mod compute_weights;
+mod custom_filter;
 mod definitions;
+mod edge_mode;
+mod error;
+mod ewa;
 mod filter_weights;
 mod fixed_point_dispatch;
 mod fixed_point_horizontal;
@@ -45,25 +52,65 @@ mod floating_point_horizontal;
 mod floating_point_vertical;
 mod handler_provider;
 mod image_size;
+mod integer_ratio;
+mod interlaced;
 mod math;
 mod mixed_storage;
 mod mlaf;
 mod resize_fixed_point;
 mod resize_floating_point;
+mod resize_linear;
 mod resize_nearest;
+mod resize_options;
+mod resize_plan;
+mod resize_weights;
 mod resizer;
+#[cfg(feature = "half")]
+mod resizer_f16;
+mod resizer_yuv;
+mod row_transform;
 mod sampler;
 mod saturate_narrow;
+mod scale_transform;
+mod source_rect;
+mod streaming;
+mod threading;
 mod trc;
 mod trc_handler;
+mod validation;
+mod yuv;
 
 pub use alpha::*;
 pub use alpha_check::{
     has_non_constant_alpha_la16, has_non_constant_alpha_la8, has_non_constant_alpha_luma_alpha_f32,
     has_non_constant_alpha_rgba16, has_non_constant_alpha_rgba8, has_non_constant_alpha_rgba_f32,
 };
+#[cfg(feature = "half")]
+pub use alpha_check::{has_non_constant_alpha_luma_alpha_f16, has_non_constant_alpha_rgba_f16};
+pub use custom_filter::{CustomFilter, CustomWindow};
+pub use edge_mode::EdgeMode;
+pub use error::{MismatchedSize, PicScaleError};
 pub use image_size::ImageSize;
+pub use interlaced::{
+    resize_plane16_fields, resize_plane16_interlaced, resize_plane8_fields,
+    resize_plane8_interlaced,
+};
+pub use resize_options::{PassOrder, PremultiplyMode, ResizeOptions};
+pub use resize_plan::{ChannelLayout, ResizePlan};
 pub use resizer::*;
-pub use sampler::ResamplingFunction;
+#[cfg(feature = "half")]
+pub use resizer_f16::*;
+pub use resizer_yuv::{
+    resample_chroma16, resample_chroma8, resize_yuv16, resize_yuv8, resize_yuv_nv16, resize_yuv_nv8,
+};
+pub use sampler::{FilterParameter, ResamplingFunction};
+pub use scale_transform::ScaleTransform;
+pub use source_rect::SourceRect;
+pub use streaming::StreamingResizer;
+pub use threading::{ThreadingPolicy, DEFAULT_MIN_PARALLEL_WORK};
 pub use trc::*;
 pub use trc_handler::*;
+pub use yuv::{
+    ChromaFormat, ChromaSiting, YuvBitPacking, YuvPlanarImage, YuvPlanarImageMut,
+    YuvSemiPlanarImage, YuvSemiPlanarImageMut, YuvSubsampling,
+};
@@ -317,20 +317,18 @@ fn qone(x: f64) -> f64 {
     (0.375 + r / s) / x
 }
 
+/// `J1(x) / x`, its limit at zero is `0.5`
 #[inline]
 pub(crate) fn jinc_f64(x: f64) -> f64 {
     if x == 0f64 {
-        return 0f64;
+        return 0.5f64;
     }
     j1(x) / x
 }
 
 #[inline]
 pub(crate) fn jinc_f32(x: f32) -> f32 {
-    if x == 0f32 {
-        return 0f32;
-    }
-    (j1(x as f64) / x as f64) as f32
+    jinc_f64(x as f64) as f32
 }
 
 pub(crate) trait Jinc<T> {
@@ -54,3 +54,24 @@ where
         0f32.as_()
     }
 }
+
+/// Sinc windowed by Blackman window with `lobes` support
+#[inline(always)]
+pub(crate) fn blackman_sinc<V: Copy + ConstPI + 'static + Mul<Output = V> + Float>(
+    x: V,
+    lobes: V,
+) -> V
+where
+    f32: AsPrimitive<V>,
+{
+    let x = x.abs();
+    if x < lobes {
+        let pi = V::const_pi();
+        let t = pi * x / lobes;
+        let window =
+            0.42f32.as_() + 0.5f32.as_() * t.cos() + 0.08f32.as_() * (2f32.as_() * t).cos();
+        sinc(pi * x) * window
+    } else {
+        0f32.as_()
+    }
+}
@@ -46,3 +46,14 @@ where
     den *= den;
     (1f32.as_() / ((2f32.as_() * pi).sqrt() * sigma)) * (-x / den).exp()
 }
+
+#[inline(always)]
+pub(crate) fn gaussian_sigma<V: Copy + Neg<Output = V> + Mul<Output = V> + 'static + Float>(
+    x: V,
+    sigma: V,
+) -> V
+where
+    f32: AsPrimitive<V>,
+{
+    (-(x * x) / (2f32.as_() * sigma * sigma)).exp()
+}
@@ -27,51 +27,49 @@
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
-use num_traits::{AsPrimitive, Float};
-use std::ops::{AddAssign, Div, Mul, MulAssign, Sub};
+use num_traits::AsPrimitive;
 
+/// Upper bound for series terms, reached only when argument is huge
+const MAX_BESSEL_ITERATIONS: usize = 1000;
+
+/// Modified Bessel function of the first kind of order zero
+///
+/// Always evaluated in `f64`, in `f32` series terms overflow for arguments above ~90.
 #[inline(always)]
-pub(crate) fn bessel_i0<
-    V: Copy + Mul<Output = V> + Div<Output = V> + MulAssign + AddAssign + 'static + PartialOrd,
->(
-    x: V,
-) -> V
-where
-    f64: AsPrimitive<V>,
-{
-    let mut s = 1.0.as_();
-    let y = x * x / 4.0.as_();
+pub(crate) fn bessel_i0(x: f64) -> f64 {
+    let mut s = 1.0;
+    let y = x * x / 4.0;
     let mut t = y;
-    let mut i: V = 2.0f64.as_();
-    while t > 1e-12.as_() {
+    let mut i = 2.0;
+    for _ in 0..MAX_BESSEL_ITERATIONS {
+        if t <= 1e-12 || !t.is_finite() {
+            break;
+        }
         s += t;
         t *= y / (i * i);
-        i += 1f64.as_();
+        i += 1.;
     }
     s
 }
 
 #[inline(always)]
-pub(crate) fn kaiser<
-    V: Copy
-        + Mul<Output = V>
-        + Div<Output = V>
-        + MulAssign
-        + AddAssign
-        + 'static
-        + PartialOrd
-        + Sub<Output = V>
-        + Float,
->(
-    x: V,
-) -> V
+pub(crate) fn kaiser_beta<V: Copy + 'static + AsPrimitive<f64>>(x: V, beta: V) -> V
 where
     f64: AsPrimitive<V>,
-    f32: AsPrimitive<V>,
 {
-    if x > 1f32.as_() {
-        return 0f32.as_();
+    let x: f64 = x.as_();
+    if x > 1. {
+        return 0f64.as_();
     }
-    let i0a = 1.0f64.as_() / bessel_i0(6.33f64.as_());
-    bessel_i0(6.33f64.as_() * (1.0f64.as_() - x * x).sqrt()) * i0a
+    let beta: f64 = beta.as_();
+    let i0a = 1.0 / bessel_i0(beta);
+    (bessel_i0(beta * (1.0 - x * x).sqrt()) * i0a).as_()
+}
+
+#[inline(always)]
+pub(crate) fn kaiser<V: Copy + 'static + AsPrimitive<f64>>(x: V) -> V
+where
+    f64: AsPrimitive<V>,
+{
+    kaiser_beta(x, 6.33f64.as_())
 }
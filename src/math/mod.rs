@@ -40,6 +40,7 @@ pub(crate) mod hann;
 pub(crate) mod kaiser;
 pub(crate) mod lagrange;
 pub(crate) mod lanczos;
+pub(crate) mod polar;
 pub(crate) mod quadric;
 pub(crate) mod sinc;
 pub(crate) mod sphinx;
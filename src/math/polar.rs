@@ -0,0 +1,82 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::math::jinc_f64;
+use std::f64::consts::PI;
+
+/// First zero of [jinc] divided by π
+const JINC_FIRST_ZERO: f64 = 1.2196698912665045;
+
+/// Zeros of [jinc] used as radii of polar Lanczos filters
+pub(crate) const JINC_RADIUS_2: f64 = 2.2331305943815286;
+pub(crate) const JINC_RADIUS_3: f64 = 3.238315484166236;
+pub(crate) const JINC_RADIUS_4: f64 = 4.24106286379607;
+pub(crate) const JINC_RADIUS_6: f64 = 6.243921689864488;
+
+/// Radially symmetric counterpart of sinc, `2 * J1(πx) / (πx)`, normalized to `1` at zero
+#[inline]
+fn jinc(x: f64) -> f64 {
+    2. * jinc_f64(PI * x)
+}
+
+/// Jinc windowed by jinc stretched to reach its first zero at `radius`
+#[inline]
+pub(crate) fn polar_lanczos(x: f64, radius: f64) -> f64 {
+    let x = x.abs();
+    if x >= radius {
+        return 0.;
+    }
+    jinc(x) * jinc(x * JINC_FIRST_ZERO / radius)
+}
+
+/// Sinc windowed by jinc over the third zero of jinc
+#[inline]
+pub(crate) fn polar_ginseng(x: f64) -> f64 {
+    let x = x.abs();
+    if x >= JINC_RADIUS_3 {
+        return 0.;
+    }
+    let sinc = if x == 0. {
+        1.
+    } else {
+        (PI * x).sin() / (PI * x)
+    };
+    sinc * jinc(x * JINC_FIRST_ZERO / JINC_RADIUS_3)
+}
+
+/// Jinc windowed by hann over the third zero of jinc, softened by `1.11`
+#[inline]
+pub(crate) fn polar_haasn_soft(x: f64) -> f64 {
+    const BLUR: f64 = 1.11;
+    let x = x.abs() / BLUR;
+    if x >= JINC_RADIUS_3 {
+        return 0.;
+    }
+    jinc(x) * (0.5 + 0.5 * (PI * x / JINC_RADIUS_3).cos())
+}
@@ -135,6 +135,14 @@ impl MixedStorage<f32> for f32 {
     }
 }
 
+#[cfg(feature = "half")]
+impl MixedStorage<half::f16> for f32 {
+    #[inline(always)]
+    fn to_mixed(self, _: u32) -> half::f16 {
+        half::f16::from_f32(self)
+    }
+}
+
 impl MixedStorage<f64> for f64 {
     #[inline(always)]
     #[allow(clippy::manual_clamp)]
@@ -26,13 +26,21 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
-use crate::compute_weights::generate_weights;
+use crate::error::PicScaleError;
 use crate::fixed_point_dispatch::{convolve_column_fixed_point, convolve_row_fixed_point};
 use crate::handler_provider::{ColumnHandlerFixedPoint, RowHandlerFixedPoint};
 use crate::image_size::ImageSize;
+use crate::integer_ratio::{resize_integer_ratio, BlockAverage};
 use crate::resize_nearest::resize_nearest;
+use crate::resize_options::ResizeOptions;
+use crate::resize_weights::ResizeWeights;
+use crate::row_transform::{convolve_transformed, RowTransform};
 use crate::sampler::ResamplingFunction;
 use crate::saturate_narrow::SaturateNarrow;
+use crate::threading::{Executor, Workers};
+use crate::validation::{
+    check_destination_layout, check_image_layout, check_layout, copy_image, scratch_slice,
+};
 use num_traits::AsPrimitive;
 use std::ops::{AddAssign, Mul};
 
@@ -45,10 +53,13 @@ use std::ops::{AddAssign, Mul};
 /// # Arguments
 ///
 /// * `src`: Source slice
+/// * `src_stride`: Source row length in elements, must be at least `width * CHANNELS`
 /// * `source_size`: Source image size
+/// * `dst_stride`: Destination row length in elements, must be at least `width * CHANNELS`
 /// * `destination_size`: Destination image size
 /// * `bit_depth`: Image bit-depth
 /// * `resampling_function`: sampler, see [ResamplingFunction] for more info
+/// * `workers`: Threads executing resizing
 ///
 /// # Generics
 ///
@@ -59,19 +70,24 @@ use std::ops::{AddAssign, Mul};
 ///
 /// If called with HDR content it will be erased.
 ///
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn resize_fixed_point<T, J, const CHANNELS: usize>(
     src: &[T],
+    src_stride: usize,
     source_size: ImageSize,
+    dst_stride: usize,
     destination_size: ImageSize,
     bit_depth: u32,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<T>, String>
+    workers: &Workers,
+) -> Result<Vec<T>, PicScaleError>
 where
     T: Copy
         + 'static
         + AsPrimitive<J>
         + Default
         + ColumnHandlerFixedPoint<T, J>
+        + BlockAverage
         + RowHandlerFixedPoint<T, J>
         + Send
         + Sync,
@@ -79,126 +95,353 @@ where
     i32: AsPrimitive<J>,
     i16: AsPrimitive<J>,
 {
-    assert!(
-        CHANNELS <= 4,
-        "Images with more than 4 channels is not supported"
-    );
-    assert_ne!(CHANNELS, 0, "Invalid count of channels");
-    if src.len() != source_size.width * CHANNELS * source_size.height {
-        return Err(format!(
-            "Source slice size must be width * channels * height ({}) but got {}",
-            source_size.width * CHANNELS * source_size.height,
-            src.len(),
-        ));
-    }
-    let (src_stride, is_stride_overflowed) = source_size.width.overflowing_mul(CHANNELS);
-    if is_stride_overflowed {
-        return Err("Stride must never exceed usize::MAX".parse().unwrap());
-    }
-    let (dst_stride, is_stride_overflowed) = destination_size.width.overflowing_mul(CHANNELS);
-    if is_stride_overflowed {
-        return Err("Stride must never exceed usize::MAX".parse().unwrap());
-    }
-    let (_, is_size_overflowing) = src_stride.overflowing_mul(source_size.height);
-    if is_size_overflowing {
-        return Err("Image size must never exceed usize::MAX".parse().unwrap());
-    }
-    let (_, is_size_overflowing) = dst_stride.overflowing_mul(destination_size.height);
-    if is_size_overflowing {
-        return Err("Image size must never exceed usize::MAX".parse().unwrap());
-    }
-    if source_size.width == 0 || source_size.height == 0 {
-        return Err("Image size must not be zero".to_string());
-    }
-    if destination_size.width == 0 || destination_size.height == 0 {
-        return Err("Image size must not be zero".to_string());
-    }
+    check_layout::<CHANNELS>(dst_stride, destination_size)?;
+    let mut store = vec![T::default(); dst_stride * destination_size.height];
+    resize_fixed_point_into::<T, J, CHANNELS>(
+        src,
+        src_stride,
+        source_size,
+        &mut store,
+        dst_stride,
+        destination_size,
+        bit_depth,
+        resampling_function,
+        &mut Vec::new(),
+        workers,
+    )?;
+    Ok(store)
+}
+
+/// Resizing using integral approximations into existing destination
+///
+/// See [resize_fixed_point] for more info.
+///
+/// # Arguments
+///
+/// * `src`: Source slice
+/// * `src_stride`: Source row length in elements, must be at least `width * CHANNELS`
+/// * `source_size`: Source image size
+/// * `dst`: Destination slice, must be at least `dst_stride * (height - 1) + width * CHANNELS`
+/// * `dst_stride`: Destination row length in elements, must be at least `width * CHANNELS`
+/// * `destination_size`: Destination image size
+/// * `bit_depth`: Image bit-depth
+/// * `resampling_function`: sampler, see [ResamplingFunction] for more info
+/// * `scratch`: Storage for intermediate image, grows when required
+/// * `workers`: Threads executing resizing
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_fixed_point_into<T, J, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+    scratch: &mut Vec<T>,
+    workers: &Workers,
+) -> Result<(), PicScaleError>
+where
+    T: Copy
+        + 'static
+        + AsPrimitive<J>
+        + Default
+        + ColumnHandlerFixedPoint<T, J>
+        + BlockAverage
+        + RowHandlerFixedPoint<T, J>
+        + Send
+        + Sync,
+    J: Copy + 'static + AsPrimitive<T> + Mul<Output = J> + AddAssign + SaturateNarrow<T> + Default,
+    i32: AsPrimitive<J>,
+    i16: AsPrimitive<J>,
+{
+    check_layout::<CHANNELS>(src_stride, source_size)?;
+    check_layout::<CHANNELS>(dst_stride, destination_size)?;
+    resampling_function.validate()?;
+    let weights = ResizeWeights::fixed_point(
+        source_size,
+        destination_size,
+        &ResizeOptions::new(resampling_function),
+    )?;
+    resize_fixed_point_with_weights::<T, J, CHANNELS>(
+        src,
+        src_stride,
+        source_size,
+        dst,
+        dst_stride,
+        destination_size,
+        bit_depth,
+        &weights,
+        scratch,
+        &workers.resize_executor(source_size, destination_size),
+    )
+}
 
-    if source_size.width == destination_size.width && source_size.height == destination_size.height
+/// Executes resizing with precomputed weights
+///
+/// # Arguments
+///
+/// * `src`: Source slice
+/// * `src_stride`: Source row length in elements, must be at least `width * CHANNELS`
+/// * `source_size`: Source image size
+/// * `dst`: Destination slice, must be at least `dst_stride * (height - 1) + width * CHANNELS`
+/// * `dst_stride`: Destination row length in elements, must be at least `width * CHANNELS`
+/// * `destination_size`: Destination image size
+/// * `bit_depth`: Image bit-depth
+/// * `weights`: Weights, must be generated for exactly `source_size` and `destination_size`
+/// * `scratch`: Storage for intermediate image, grows when required
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_fixed_point_with_weights<T, J, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    weights: &ResizeWeights<i16>,
+    scratch: &mut Vec<T>,
+    executor: &Executor,
+) -> Result<(), PicScaleError>
+where
+    T: Copy
+        + 'static
+        + AsPrimitive<J>
+        + Default
+        + ColumnHandlerFixedPoint<T, J>
+        + BlockAverage
+        + RowHandlerFixedPoint<T, J>
+        + Send
+        + Sync,
+    J: Copy + 'static + AsPrimitive<T> + Mul<Output = J> + AddAssign + SaturateNarrow<T> + Default,
+    i32: AsPrimitive<J>,
+    i16: AsPrimitive<J>,
+{
+    let src = check_image_layout::<T, CHANNELS>(src, src_stride, source_size)?;
+    let dst = check_destination_layout::<T, CHANNELS>(dst, dst_stride, destination_size)?;
+
+    let (vertical_filters, horizontal_filters, horizontal_first) = match weights {
+        ResizeWeights::Copy => {
+            copy_image::<T, CHANNELS>(src, src_stride, dst, dst_stride, source_size);
+            return Ok(());
+        }
+        ResizeWeights::Nearest { columns, rows } => {
+            resize_nearest::<T, CHANNELS>(
+                src, src_stride, dst, dst_stride, columns, rows, executor,
+            );
+            return Ok(());
+        }
+        ResizeWeights::IntegerRatio { ratio, .. } => {
+            resize_integer_ratio::<T, CHANNELS>(
+                src,
+                src_stride,
+                dst,
+                dst_stride,
+                destination_size,
+                *ratio,
+                executor,
+            );
+            return Ok(());
+        }
+        ResizeWeights::Convolution {
+            vertical,
+            horizontal,
+            horizontal_first,
+        } => (vertical.as_ref(), horizontal.as_ref(), *horizontal_first),
+    };
+
+    if let (Some(vertical_filters), Some(horizontal_filters), true) =
+        (vertical_filters, horizontal_filters, horizontal_first)
     {
-        return Ok(src.to_vec());
-    }
+        let new_horizontal_size = ImageSize::new(destination_size.width, source_size.height);
 
-    if resampling_function == ResamplingFunction::Nearest {
-        let mut store =
-            vec![T::default(); destination_size.width * destination_size.height * CHANNELS];
-        resize_nearest::<T, CHANNELS>(
+        // Intermediate image is always tightly packed
+        let new_stride = destination_size.width * CHANNELS;
+        let transient = scratch_slice(scratch, new_stride * source_size.height);
+
+        convolve_row_fixed_point::<T, J, CHANNELS>(
             src,
-            source_size.width,
-            source_size.height,
-            &mut store,
-            destination_size.width,
-            destination_size.height,
-        );
-
-        assert_eq!(
-            store.len(),
-            destination_size.width * destination_size.height * CHANNELS,
-            "The resized image must always have valid target dimensions"
-        );
-
-        return Ok(store);
+            src_stride,
+            source_size,
+            horizontal_filters,
+            transient,
+            new_stride,
+            new_horizontal_size,
+            bit_depth,
+            executor,
+        )?;
+
+        convolve_column_fixed_point::<T, J, CHANNELS>(
+            transient,
+            new_stride,
+            new_horizontal_size,
+            vertical_filters,
+            dst,
+            dst_stride,
+            destination_size,
+            bit_depth,
+            executor,
+        )?;
+        return Ok(());
     }
 
     let mut working_slice_size = source_size;
     let mut working_slice_ref = src;
+    let mut working_stride = src_stride;
 
-    let mut transient = vec![];
-
-    if working_slice_size.height != destination_size.height {
-        let vertical_filters = generate_weights::<f32>(
-            resampling_function,
-            working_slice_size.height,
-            destination_size.height,
-        );
+    if let Some(vertical_filters) = vertical_filters {
+        let new_vertical_size = ImageSize::new(working_slice_size.width, destination_size.height);
 
-        transient =
-            vec![T::default(); working_slice_size.width * destination_size.height * CHANNELS];
+        if horizontal_filters.is_none() {
+            convolve_column_fixed_point::<T, J, CHANNELS>(
+                working_slice_ref,
+                working_stride,
+                working_slice_size,
+                vertical_filters,
+                dst,
+                dst_stride,
+                new_vertical_size,
+                bit_depth,
+                executor,
+            )?;
+            return Ok(());
+        }
 
-        let new_vertical_size = ImageSize::new(working_slice_size.width, destination_size.height);
+        // Intermediate image is always tightly packed
+        let new_stride = working_slice_size.width * CHANNELS;
+        let transient = scratch_slice(scratch, new_stride * destination_size.height);
 
         convolve_column_fixed_point::<T, J, CHANNELS>(
             working_slice_ref,
+            working_stride,
             working_slice_size,
             vertical_filters,
-            &mut transient,
+            transient,
+            new_stride,
             new_vertical_size,
             bit_depth,
-        );
+            executor,
+        )?;
 
         working_slice_size = new_vertical_size;
-        working_slice_ref = &transient;
+        working_slice_ref = transient;
+        working_stride = new_stride;
     }
 
-    if working_slice_size.width != destination_size.width {
-        let vertical_filters = generate_weights::<f32>(
-            resampling_function,
-            working_slice_size.width,
-            destination_size.width,
-        );
+    let Some(horizontal_filters) = horizontal_filters else {
+        return Ok(());
+    };
 
-        let mut transient2 =
-            vec![T::default(); destination_size.width * working_slice_size.height * CHANNELS];
+    convolve_row_fixed_point::<T, J, CHANNELS>(
+        working_slice_ref,
+        working_stride,
+        working_slice_size,
+        horizontal_filters,
+        dst,
+        dst_stride,
+        destination_size,
+        bit_depth,
+        executor,
+    )?;
 
-        let new_vertical_size = ImageSize::new(destination_size.width, working_slice_size.height);
+    Ok(())
+}
 
-        convolve_row_fixed_point::<T, J, CHANNELS>(
-            working_slice_ref,
-            working_slice_size,
-            vertical_filters,
-            &mut transient2,
-            new_vertical_size,
-            bit_depth,
-        );
+/// Executes resizing with precomputed weights, converting rows on the fly
+///
+/// Each row is converted with `transform` into intermediate type `I` when loaded, and back
+/// when stored, see [convolve_transformed] for more info.
+///
+/// # Arguments
+///
+/// * `bit_depth`: Bit-depth of the intermediate
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_fixed_point_transformed<T, I, J, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    weights: &ResizeWeights<i16>,
+    transform: &impl RowTransform<T, I>,
+    scratch: &mut Vec<I>,
+    executor: &Executor,
+) -> Result<(), PicScaleError>
+where
+    T: Copy + Default + Send + Sync,
+    I: Copy
+        + 'static
+        + AsPrimitive<J>
+        + Default
+        + ColumnHandlerFixedPoint<I, J>
+        + RowHandlerFixedPoint<I, J>
+        + Send
+        + Sync,
+    J: Copy + 'static + AsPrimitive<I> + Mul<Output = J> + AddAssign + SaturateNarrow<I> + Default,
+    i32: AsPrimitive<J>,
+    i16: AsPrimitive<J>,
+{
+    let src = check_image_layout::<T, CHANNELS>(src, src_stride, source_size)?;
+    let dst = check_destination_layout::<T, CHANNELS>(dst, dst_stride, destination_size)?;
 
-        transient = transient2;
-    }
+    let (vertical_filters, horizontal_filters) = match weights {
+        // Copy and nearest take source pixels as is, so conversion is not required
+        ResizeWeights::Copy => {
+            copy_image::<T, CHANNELS>(src, src_stride, dst, dst_stride, source_size);
+            return Ok(());
+        }
+        ResizeWeights::Nearest { columns, rows } => {
+            resize_nearest::<T, CHANNELS>(
+                src, src_stride, dst, dst_stride, columns, rows, executor,
+            );
+            return Ok(());
+        }
+        // Fast paths can't convert rows, so general weights are executed
+        ResizeWeights::IntegerRatio { general, .. } => {
+            return resize_fixed_point_transformed::<T, I, J, CHANNELS>(
+                src,
+                src_stride,
+                source_size,
+                dst,
+                dst_stride,
+                destination_size,
+                bit_depth,
+                general,
+                transform,
+                scratch,
+                executor,
+            );
+        }
+        // Rows are converted while loaded, so horizontal pass always goes first
+        ResizeWeights::Convolution {
+            vertical,
+            horizontal,
+            ..
+        } => (vertical.as_ref(), horizontal.as_ref()),
+    };
 
-    assert_eq!(
-        transient.len(),
-        destination_size.width * destination_size.height * CHANNELS,
-        "The resized image must always have valid target dimensions"
+    convolve_transformed::<T, I, i16, CHANNELS>(
+        src,
+        src_stride,
+        source_size,
+        dst,
+        dst_stride,
+        destination_size,
+        vertical_filters,
+        horizontal_filters,
+        transform,
+        |src, dst, weights| I::handle_row::<CHANNELS>(src, dst, weights, bit_depth),
+        |bounds, src, dst, src_stride, weights| {
+            I::handle_column(bounds, src, dst, src_stride, weights, bit_depth)
+        },
+        scratch,
+        executor,
     );
 
-    Ok(transient)
+    Ok(())
 }
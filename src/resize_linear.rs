@@ -0,0 +1,198 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::alpha_check::has_constant_alpha;
+use crate::error::PicScaleError;
+use crate::image_size::ImageSize;
+use crate::resize_fixed_point::resize_fixed_point_transformed;
+use crate::resize_options::ResizeOptions;
+use crate::resize_weights::ResizeWeights;
+use crate::row_transform::RowTransform;
+use crate::sampler::ResamplingFunction;
+use crate::threading::Workers;
+use crate::validation::{check_image_layout, check_layout};
+use crate::TransferFunction;
+
+/// Bit-depth of linear intermediate image
+const LINEAR_BIT_DEPTH: u32 = 12;
+const LINEAR_MAX: usize = (1 << LINEAR_BIT_DEPTH) - 1;
+
+/// Lookup tables to move 8-bit image into linear intermediate and back
+///
+/// When `associate` is set, color is multiplied by alpha after linearization and divided
+/// back before encoding, so alpha is associated in linear light, not with gamma-encoded values.
+struct LinearTables<const CHANNELS: usize> {
+    linearize: [u16; 256],
+    gamma: Vec<u8>,
+    alpha_to: [u16; 256],
+    alpha_from: Vec<u8>,
+    associate: bool,
+}
+
+impl<const CHANNELS: usize> LinearTables<CHANNELS> {
+    fn new(trc: TransferFunction, associate: bool) -> LinearTables<CHANNELS> {
+        let mut linearize = [0u16; 256];
+        let mut alpha_to = [0u16; 256];
+        for (i, (linear, alpha)) in linearize.iter_mut().zip(alpha_to.iter_mut()).enumerate() {
+            let v = i as f32 * (1. / 255.);
+            *linear = (trc.linearize(v) * LINEAR_MAX as f32)
+                .round()
+                .min(LINEAR_MAX as f32) as u16;
+            *alpha = ((i * LINEAR_MAX + 127) / 255) as u16;
+        }
+        let mut gamma = vec![0u8; LINEAR_MAX + 1];
+        let mut alpha_from = vec![0u8; LINEAR_MAX + 1];
+        for (i, (gamma, alpha)) in gamma.iter_mut().zip(alpha_from.iter_mut()).enumerate() {
+            let v = i as f32 * (1. / LINEAR_MAX as f32);
+            *gamma = (trc.gamma(v) * 255.).round().min(255.) as u8;
+            *alpha = ((i * 255 + LINEAR_MAX / 2) / LINEAR_MAX) as u8;
+        }
+        LinearTables {
+            linearize,
+            gamma,
+            alpha_to,
+            alpha_from,
+            associate,
+        }
+    }
+}
+
+impl<const CHANNELS: usize> RowTransform<u8, u16> for LinearTables<CHANNELS> {
+    /// Converts row into linear, on 2 and 4 channels alpha will be considered as last item
+    fn load(&self, src: &[u8], dst: &mut [u16]) {
+        let has_alpha = CHANNELS == 2 || CHANNELS == 4;
+        for (dst, src) in dst
+            .chunks_exact_mut(CHANNELS)
+            .zip(src.chunks_exact(CHANNELS))
+        {
+            if has_alpha && self.associate {
+                let alpha = self.alpha_to[src[CHANNELS - 1] as usize] as usize;
+                for (dst, &src) in dst.iter_mut().zip(src[..CHANNELS - 1].iter()) {
+                    let linear = self.linearize[src as usize] as usize;
+                    *dst = ((linear * alpha + LINEAR_MAX / 2) / LINEAR_MAX) as u16;
+                }
+                dst[CHANNELS - 1] = alpha as u16;
+                continue;
+            }
+            for (c, (dst, &src)) in dst.iter_mut().zip(src).enumerate() {
+                *dst = if has_alpha && c == CHANNELS - 1 {
+                    self.alpha_to[src as usize]
+                } else {
+                    self.linearize[src as usize]
+                };
+            }
+        }
+    }
+
+    /// Converts linear row back into gamma, on 2 and 4 channels alpha will be considered as last item
+    fn store(&self, src: &[u16], dst: &mut [u8]) {
+        let has_alpha = CHANNELS == 2 || CHANNELS == 4;
+        for (dst, src) in dst
+            .chunks_exact_mut(CHANNELS)
+            .zip(src.chunks_exact(CHANNELS))
+        {
+            if has_alpha && self.associate {
+                let alpha = (src[CHANNELS - 1] as usize).min(LINEAR_MAX);
+                for (dst, &src) in dst.iter_mut().zip(src[..CHANNELS - 1].iter()) {
+                    let linear = (src as usize * LINEAR_MAX + alpha / 2)
+                        .checked_div(alpha)
+                        .map_or(0, |linear| linear.min(LINEAR_MAX));
+                    *dst = self.gamma[linear];
+                }
+                dst[CHANNELS - 1] = self.alpha_from[alpha];
+                continue;
+            }
+            for (c, (dst, &src)) in dst.iter_mut().zip(src).enumerate() {
+                let src = (src as usize).min(LINEAR_MAX);
+                *dst = if has_alpha && c == CHANNELS - 1 {
+                    self.alpha_from[src]
+                } else {
+                    self.gamma[src]
+                };
+            }
+        }
+    }
+}
+
+/// Resizing 8-bit image in linear light
+///
+/// Image is linearized into 12 bit-depth intermediate while horizontal pass reads it,
+/// and encoded back with `trc` when vertical pass stores the result, so there is no separate
+/// pass over the full image to convert it. Alpha channel is not linearized,
+/// but when it is not constant it is associated with linear color for the time of resizing.
+/// See [resize_fixed_point_transformed] for more info.
+///
+/// # Arguments
+///
+/// * `src`: Source slice
+/// * `src_stride`: Source row length in elements, must be at least `width * CHANNELS`
+/// * `source_size`: Source image size
+/// * `dst`: Destination slice, must be at least `dst_stride * (height - 1) + width * CHANNELS`
+/// * `dst_stride`: Destination row length in elements, must be at least `width * CHANNELS`
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: sampler, see [ResamplingFunction] for more info
+/// * `trc`: Transfer function of the image, see [TransferFunction] for more info
+/// * `scratch`: Storage for intermediate image, grows when required
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resize_linear_into<const CHANNELS: usize>(
+    src: &[u8],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    trc: TransferFunction,
+    scratch: &mut Vec<u16>,
+) -> Result<(), PicScaleError> {
+    let src = check_image_layout::<u8, CHANNELS>(src, src_stride, source_size)?;
+    check_layout::<CHANNELS>(dst_stride, destination_size)?;
+    resampling_function.validate()?;
+    let weights = ResizeWeights::fixed_point(
+        source_size,
+        destination_size,
+        &ResizeOptions::new(resampling_function),
+    )?;
+    let associate = (CHANNELS == 2 || CHANNELS == 4)
+        && !has_constant_alpha::<u8, CHANNELS>(src, src_stride, source_size);
+    let tables = LinearTables::<CHANNELS>::new(trc, associate);
+    resize_fixed_point_transformed::<u8, u16, i32, CHANNELS>(
+        src,
+        src_stride,
+        source_size,
+        dst,
+        dst_stride,
+        destination_size,
+        LINEAR_BIT_DEPTH,
+        &weights,
+        &tables,
+        scratch,
+        &Workers::default().resize_executor(source_size, destination_size),
+    )
+}
@@ -26,47 +26,69 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::edge_mode::EdgeMode;
+use crate::source_rect::AxisMapping;
+use crate::threading::Executor;
 #[cfg(feature = "rayon")]
 use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 #[cfg(feature = "rayon")]
 use rayon::prelude::ParallelSliceMut;
 
-pub(crate) fn resize_nearest<T: Copy + Send + Sync, const CHANNELS: usize>(
+/// Computes source pixel for each destination pixel along one axis,
+/// `None` is for transparent pixels outside the source
+pub(crate) fn nearest_indices(
+    in_size: usize,
+    out_size: usize,
+    mapping: Option<AxisMapping>,
+    edge_mode: EdgeMode,
+) -> Vec<Option<usize>> {
+    match mapping {
+        None => {
+            const SHIFT: i32 = 32;
+
+            let k: u64 = ((in_size as u64) << SHIFT) / out_size as u64;
+            let k_half: u64 = k >> 1;
+            (0..out_size as u64)
+                .map(|i| Some(((i * k + k_half) >> SHIFT) as usize))
+                .collect()
+        }
+        Some(mapping) => (0..out_size)
+            .map(|i| {
+                let x = (mapping.offset + (i as f64 + 0.5) * mapping.scale).floor() as i64;
+                if edge_mode == EdgeMode::Transparent && (x < 0 || x >= in_size as i64) {
+                    None
+                } else {
+                    Some(edge_mode.map(x, in_size))
+                }
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn resize_nearest<T: Copy + Default + Send + Sync, const CHANNELS: usize>(
     src: &[T],
-    src_width: usize,
-    src_height: usize,
+    src_stride: usize,
     dst: &mut [T],
-    dst_width: usize,
-    dst_height: usize,
+    dst_stride: usize,
+    columns: &[Option<usize>],
+    rows: &[Option<usize>],
+    executor: &Executor,
 ) {
-    const SHIFT: i32 = 32;
-
-    let k_x: u64 = ((src_width as u64) << SHIFT) / dst_width as u64;
-    let k_y: u64 = ((src_height as u64) << SHIFT) / dst_height as u64;
-    let k_x_half: u64 = k_x >> 1;
-    let k_y_half: u64 = k_y >> 1;
-
-    let dst_stride = dst_width * CHANNELS;
-    let src_stride = src_width * CHANNELS;
+    let dst_row_length = columns.len() * CHANNELS;
 
-    let iter;
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = dst.chunks_exact_mut(dst_stride);
-    }
-    #[cfg(feature = "rayon")]
-    {
-        iter = dst.par_chunks_exact_mut(dst_stride);
-    }
-
-    iter.enumerate().for_each(|(y, dst_row)| {
-        let src_y = ((y as u64 * k_y + k_y_half) >> SHIFT) as usize;
+    let copy_row = |(dst_row, &src_y): (&mut [T], &Option<usize>)| {
+        let dst_row = &mut dst_row[..dst_row_length];
+        let Some(src_y) = src_y else {
+            dst_row.fill(T::default());
+            return;
+        };
         let src_offset_y = src_y * src_stride;
 
-        let mut src_x_fixed = k_x_half;
-        for dst_chunk in dst_row.chunks_exact_mut(CHANNELS) {
-            let src_x = (src_x_fixed >> SHIFT) as usize;
-
+        for (dst_chunk, &src_x) in dst_row.chunks_exact_mut(CHANNELS).zip(columns) {
+            let Some(src_x) = src_x else {
+                dst_chunk.fill(T::default());
+                continue;
+            };
             let src_px = src_x * CHANNELS;
             let offset = src_offset_y + src_px;
 
@@ -75,8 +97,18 @@ pub(crate) fn resize_nearest<T: Copy + Send + Sync, const CHANNELS: usize>(
             for (src, dst) in src_slice.iter().zip(dst_chunk.iter_mut()) {
                 *dst = *src;
             }
-
-            src_x_fixed += k_x;
         }
-    });
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            dst.par_chunks_mut(dst_stride)
+                .with_min_len(executor.min_len(columns.len()))
+                .zip(rows)
+                .for_each(copy_row);
+        });
+    } else {
+        dst.chunks_mut(dst_stride).zip(rows).for_each(copy_row);
+    }
 }
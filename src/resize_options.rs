@@ -0,0 +1,238 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::edge_mode::EdgeMode;
+use crate::error::PicScaleError;
+use crate::ewa::is_polar_filter;
+use crate::image_size::ImageSize;
+use crate::sampler::ResamplingFunction;
+use crate::scale_transform::ScaleTransform;
+use crate::source_rect::{AxisMapping, SourceRect};
+
+/// Describes how alpha channel is handled while resizing
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PremultiplyMode {
+    /// Image is resized as is, if it has alpha channel
+    /// caller is responsible to associate alpha before resizing and un-associate after
+    #[default]
+    Disabled,
+    /// Alpha is associated while rows are loaded and un-associated when rows are stored.
+    /// Work is skipped when alpha channel is constant across the image.
+    ///
+    /// Applies only to layouts with alpha, alpha is always considered as the last channel.
+    Auto,
+}
+
+/// Order of separable passes
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum PassOrder {
+    /// Order with less multiply-accumulate operations is chosen from kernel sizes
+    /// and intermediate image size
+    #[default]
+    Auto,
+    /// Vertical pass goes first, then horizontal
+    VerticalFirst,
+    /// Horizontal pass goes first, then vertical
+    HorizontalFirst,
+}
+
+/// Options for [crate::ResizePlan]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ResizeOptions {
+    /// Resampling filter, see [ResamplingFunction] for more info
+    pub resampling_function: ResamplingFunction,
+    /// Alpha handling, see [PremultiplyMode] for more info
+    pub premultiply: PremultiplyMode,
+    /// Sampling outside the image, see [EdgeMode] for more info
+    pub edge_mode: EdgeMode,
+    /// Region of the source to resize, `None` resizes the whole image
+    pub source_rect: Option<SourceRect>,
+    /// Arbitrary scale and offset, `None` stretches destination over the source.
+    /// Can't be combined with `source_rect`.
+    pub scale_transform: Option<ScaleTransform>,
+    /// Kernel support multiplier, `1` keeps filter as is.
+    /// Values above `1` are softening, below `1` are sharpening, but increase aliasing.
+    /// Area downscale is a box filter and is affected as well, area upscale interpolates
+    /// between two nearest pixels and is not.
+    ///
+    /// Must be positive and at most [ResizeOptions::MAX_BLUR].
+    pub blur: f32,
+    /// Part of the kernel support in range `0..=0.9` where kernel is flat,
+    /// rest of the kernel is squeezed to keep the support.
+    /// See [ResizeOptions::MAX_TAPER].
+    pub taper: f32,
+    /// Strength in range `0..=1` of clamping each output sample into the range of
+    /// its nearest source taps, `0` disables it.
+    /// Suppresses halos of filters with negative lobes, such as Lanczos or Catmull-Rom.
+    pub anti_ringing: f32,
+    /// Evaluates filter radially over 2D neighbourhood with elliptical weighted averaging
+    /// instead of two separable passes.
+    /// Available only for jinc-based filters: [ResamplingFunction::Lanczos2Jinc],
+    /// [ResamplingFunction::Lanczos3Jinc], [ResamplingFunction::Lanczos4Jinc],
+    /// [ResamplingFunction::Lanczos6Jinc], [ResamplingFunction::Ginseng]
+    /// and [ResamplingFunction::HaasnSoft].
+    pub ewa: bool,
+    /// Order of separable passes, see [PassOrder] for more info.
+    /// Results of both orders are slightly different because of rounding of the intermediate,
+    /// fix the order when reproducible output is required.
+    ///
+    /// When alpha is associated on the fly, horizontal pass always goes first.
+    pub pass_order: PassOrder,
+}
+
+impl Default for ResizeOptions {
+    fn default() -> Self {
+        ResizeOptions {
+            resampling_function: ResamplingFunction::default(),
+            premultiply: PremultiplyMode::default(),
+            edge_mode: EdgeMode::default(),
+            source_rect: None,
+            scale_transform: None,
+            blur: 1.,
+            taper: 0.,
+            anti_ringing: 0.,
+            ewa: false,
+            pass_order: PassOrder::Auto,
+        }
+    }
+}
+
+impl ResizeOptions {
+    /// Largest blur, kernel is widened by blur and so is the amount of taps
+    pub const MAX_BLUR: f32 = 16.;
+
+    /// Largest taper, remaining part of the kernel is squeezed down to a tenth of the support
+    pub const MAX_TAPER: f32 = 0.9;
+
+    /// Creates options with resampling filter, everything else is default
+    pub fn new(resampling_function: ResamplingFunction) -> ResizeOptions {
+        ResizeOptions {
+            resampling_function,
+            ..Default::default()
+        }
+    }
+
+    /// Sets alpha handling, see [PremultiplyMode] for more info
+    pub fn with_premultiply(self, premultiply: PremultiplyMode) -> ResizeOptions {
+        ResizeOptions {
+            premultiply,
+            ..self
+        }
+    }
+
+    /// Sets how kernel samples outside the image, see [EdgeMode] for more info
+    pub fn with_edge_mode(self, edge_mode: EdgeMode) -> ResizeOptions {
+        ResizeOptions { edge_mode, ..self }
+    }
+
+    /// Resizes only a region of the source, see [SourceRect] for more info
+    pub fn with_source_rect(self, source_rect: SourceRect) -> ResizeOptions {
+        ResizeOptions {
+            source_rect: Some(source_rect),
+            ..self
+        }
+    }
+
+    /// Places destination over the source, see [ScaleTransform] for more info
+    pub fn with_scale_transform(self, scale_transform: ScaleTransform) -> ResizeOptions {
+        ResizeOptions {
+            scale_transform: Some(scale_transform),
+            ..self
+        }
+    }
+
+    /// Sets kernel support multiplier, applies to every filter except nearest
+    /// and upscaling with area
+    pub fn with_blur(self, blur: f32) -> ResizeOptions {
+        ResizeOptions { blur, ..self }
+    }
+
+    /// Sets flat part of the kernel, applies to every filter except nearest
+    /// and upscaling with area
+    pub fn with_taper(self, taper: f32) -> ResizeOptions {
+        ResizeOptions { taper, ..self }
+    }
+
+    /// Sets anti-ringing strength, applies to every filter except nearest
+    /// and upscaling with area
+    pub fn with_anti_ringing(self, anti_ringing: f32) -> ResizeOptions {
+        ResizeOptions {
+            anti_ringing,
+            ..self
+        }
+    }
+
+    /// Enables elliptical weighted averaging, applies only to jinc-based filters
+    pub fn with_ewa(self, ewa: bool) -> ResizeOptions {
+        ResizeOptions { ewa, ..self }
+    }
+
+    /// Sets order of separable passes, see [PassOrder] for more info
+    pub fn with_pass_order(self, pass_order: PassOrder) -> ResizeOptions {
+        ResizeOptions { pass_order, ..self }
+    }
+
+    /// Checks whether kernel shape is changed by blur or taper
+    pub(crate) fn is_kernel_shaped(&self) -> bool {
+        self.blur != 1. || self.taper != 0.
+    }
+
+    pub(crate) fn validate(&self, source_size: ImageSize) -> Result<(), PicScaleError> {
+        self.resampling_function.validate()?;
+        let valid_blur = self.blur > 0. && self.blur <= ResizeOptions::MAX_BLUR;
+        let valid_taper = (0. ..=ResizeOptions::MAX_TAPER).contains(&self.taper);
+        let valid_anti_ringing = (0. ..=1.).contains(&self.anti_ringing);
+        let valid_ewa = !self.ewa || is_polar_filter(self.resampling_function);
+        if !valid_blur || !valid_taper || !valid_anti_ringing || !valid_ewa {
+            return Err(PicScaleError::InvalidResamplingFunction);
+        }
+        if let Some(source_rect) = self.source_rect {
+            source_rect.validate(source_size)?;
+        }
+        if let Some(scale_transform) = self.scale_transform {
+            if self.source_rect.is_some() {
+                return Err(PicScaleError::InvalidScaleTransform);
+            }
+            scale_transform.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Horizontal and vertical mappings of destination onto the source,
+    /// `None` when destination is stretched over the whole source
+    pub(crate) fn axis_mappings(
+        &self,
+        destination_size: ImageSize,
+    ) -> Option<(AxisMapping, AxisMapping)> {
+        if let Some(source_rect) = self.source_rect {
+            return Some(source_rect.axis_mappings(destination_size));
+        }
+        self.scale_transform
+            .map(|scale_transform| scale_transform.axis_mappings())
+    }
+}
@@ -0,0 +1,703 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::alpha_check::has_constant_alpha;
+use crate::alpha_transform::AlphaTransform;
+use crate::error::PicScaleError;
+use crate::ewa::{resize_ewa, EwaWeights};
+use crate::image_size::ImageSize;
+use crate::resize_fixed_point::{resize_fixed_point_transformed, resize_fixed_point_with_weights};
+use crate::resize_floating_point::{
+    resize_floating_point_transformed, resize_floating_point_with_weights,
+};
+use crate::resize_options::{PremultiplyMode, ResizeOptions};
+use crate::resize_weights::ResizeWeights;
+use crate::sampler::ResamplingFunction;
+use crate::threading::{ThreadingPolicy, Workers};
+use crate::validation::{check_bit_depth, check_image_layout, check_layout};
+use std::marker::PhantomData;
+
+/// Layout of interleaved pixel channels
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ChannelLayout {
+    /// Single channel image
+    Plane,
+    /// Single channel image with alpha, `[luma, alpha]`
+    PlaneWithAlpha,
+    /// `[r, g, b]` or any other 3 channels image
+    Rgb,
+    /// `[r, g, b, a]` or any other 4 channels image
+    Rgba,
+    /// Image with any channels count in range `1..=8`, such as CMYK with alpha.
+    ///
+    /// Every channel is resampled independently and none of them is considered as alpha,
+    /// so [PremultiplyMode::Auto] has no effect.
+    Interleaved(usize),
+}
+
+impl ChannelLayout {
+    /// Most channels [ChannelLayout::Interleaved] might have
+    pub const MAX_CHANNELS: usize = 8;
+
+    /// Returns count of channels
+    pub const fn channels(&self) -> usize {
+        match self {
+            ChannelLayout::Plane => 1,
+            ChannelLayout::PlaneWithAlpha => 2,
+            ChannelLayout::Rgb => 3,
+            ChannelLayout::Rgba => 4,
+            ChannelLayout::Interleaved(channels) => *channels,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum PlanWeights {
+    FixedPoint(ResizeWeights<i16>),
+    FloatingPoint(ResizeWeights<f32>),
+    Ewa(EwaWeights),
+}
+
+impl PlanWeights {
+    /// Elliptical weighted averaging weights when requested, otherwise separable ones
+    fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+        separable: impl FnOnce() -> Result<PlanWeights, PicScaleError>,
+    ) -> Result<PlanWeights, PicScaleError> {
+        if options.ewa {
+            EwaWeights::new(source_size, destination_size, options).map(PlanWeights::Ewa)
+        } else {
+            separable()
+        }
+    }
+}
+
+/// Reusable resizing plan
+///
+/// Filter weights for both passes are computed once on creation, and then the plan might be
+/// executed any amount of times for images with the same source and destination sizes.
+/// Plan is immutable after creation, so it might be shared between threads.
+///
+/// Plan is available for `u8`, `u16` and `f32` images, and `f16` with `half` feature.
+///
+/// Plan is also the entry point for images with padded rows, see [ResizePlan::resize_with_stride],
+/// free functions such as [crate::resize_rgba8] accept only tightly packed images.
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{ChannelLayout, ImageSize, ResamplingFunction, ResizePlan};
+///
+/// let source_size = ImageSize::new(64, 64);
+/// let destination_size = ImageSize::new(32, 32);
+/// let plan = ResizePlan::<u8>::new(
+///     source_size,
+///     destination_size,
+///     ChannelLayout::Rgba,
+///     ResamplingFunction::Lanczos3,
+/// )
+/// .unwrap();
+/// let source = vec![0u8; 64 * 64 * 4];
+/// let mut destination = vec![0u8; 32 * 32 * 4];
+/// let mut scratch = Vec::new();
+/// for _ in 0..3 {
+///     plan.resize_with_scratch(&source, &mut destination, &mut scratch)
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResizePlan<T> {
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    layout: ChannelLayout,
+    bit_depth: u32,
+    options: ResizeOptions,
+    weights: PlanWeights,
+    workers: Workers,
+    _phantom: PhantomData<T>,
+}
+
+fn check_plan_sizes(
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    layout: ChannelLayout,
+    options: &ResizeOptions,
+) -> Result<(), PicScaleError> {
+    let check = match layout.channels() {
+        1 => check_layout::<1>,
+        2 => check_layout::<2>,
+        3 => check_layout::<3>,
+        4 => check_layout::<4>,
+        5 => check_layout::<5>,
+        6 => check_layout::<6>,
+        7 => check_layout::<7>,
+        8 => check_layout::<8>,
+        channels => return Err(PicScaleError::UnsupportedChannelsCount(channels)),
+    };
+    let channels = layout.channels();
+    let row_length = |size: ImageSize| {
+        size.width
+            .checked_mul(channels)
+            .ok_or(PicScaleError::ImageSizeOverflow)
+    };
+    check(row_length(source_size)?, source_size)?;
+    check(row_length(destination_size)?, destination_size)?;
+    options.validate(source_size)
+}
+
+impl<T> ResizePlan<T> {
+    fn with_weights(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        bit_depth: u32,
+        options: ResizeOptions,
+        weights: PlanWeights,
+    ) -> ResizePlan<T> {
+        ResizePlan {
+            source_size,
+            destination_size,
+            layout,
+            bit_depth,
+            options,
+            weights,
+            workers: Workers::default(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Source image size this plan was created for
+    pub fn source_size(&self) -> ImageSize {
+        self.source_size
+    }
+
+    /// Destination image size this plan was created for
+    pub fn destination_size(&self) -> ImageSize {
+        self.destination_size
+    }
+
+    /// Channel layout this plan was created for
+    pub fn layout(&self) -> ChannelLayout {
+        self.layout
+    }
+
+    /// Image bit-depth this plan was created for
+    pub fn bit_depth(&self) -> u32 {
+        self.bit_depth
+    }
+
+    /// Resampling filter this plan was created for
+    pub fn resampling_function(&self) -> ResamplingFunction {
+        self.options.resampling_function
+    }
+
+    /// Options this plan was created with
+    pub fn options(&self) -> &ResizeOptions {
+        &self.options
+    }
+
+    /// Sets threads executing this plan, see [ThreadingPolicy] for more info
+    ///
+    /// Pool for [ThreadingPolicy::Threads] is created right away and shared by clones of the plan.
+    pub fn with_threading(self, policy: ThreadingPolicy) -> Result<ResizePlan<T>, PicScaleError> {
+        let workers =
+            Workers::new(policy)?.with_min_parallel_work(self.workers.min_parallel_work());
+        Ok(ResizePlan { workers, ..self })
+    }
+
+    /// Sets least amount of pixels worth splitting across threads,
+    /// default is [crate::DEFAULT_MIN_PARALLEL_WORK]
+    ///
+    /// Images with smaller `source + destination` area run on the calling thread,
+    /// larger ones are split into tasks of at least this size.
+    pub fn with_min_parallel_work(self, pixels: usize) -> ResizePlan<T> {
+        ResizePlan {
+            workers: self.workers.with_min_parallel_work(pixels),
+            ..self
+        }
+    }
+
+    /// Threads executing this plan
+    pub fn threading(&self) -> &ThreadingPolicy {
+        self.workers.policy()
+    }
+
+    /// Least amount of pixels worth splitting across threads
+    pub fn min_parallel_work(&self) -> usize {
+        self.workers.min_parallel_work()
+    }
+
+    pub(crate) fn weights(&self) -> &PlanWeights {
+        &self.weights
+    }
+}
+
+impl<T: Copy + PartialEq> ResizePlan<T> {
+    /// Checks if alpha must be associated on the fly for this source
+    fn needs_premultiplication(
+        &self,
+        source: &[T],
+        source_stride: usize,
+    ) -> Result<bool, PicScaleError> {
+        if self.options.premultiply == PremultiplyMode::Disabled {
+            return Ok(false);
+        }
+        match self.layout {
+            ChannelLayout::PlaneWithAlpha => {
+                let source = check_image_layout::<T, 2>(source, source_stride, self.source_size)?;
+                Ok(!has_constant_alpha::<T, 2>(
+                    source,
+                    source_stride,
+                    self.source_size,
+                ))
+            }
+            ChannelLayout::Rgba => {
+                let source = check_image_layout::<T, 4>(source, source_stride, self.source_size)?;
+                Ok(!has_constant_alpha::<T, 4>(
+                    source,
+                    source_stride,
+                    self.source_size,
+                ))
+            }
+            ChannelLayout::Plane | ChannelLayout::Rgb | ChannelLayout::Interleaved(_) => Ok(false),
+        }
+    }
+}
+
+/// Dispatches by channels count, layouts are validated on plan creation
+macro_rules! dispatch_layout {
+    ($layout:expr, $method:ident, $($generic:ty),* ; $($arg:expr),* $(,)?) => {
+        match $layout.channels() {
+            1 => $method::<$($generic),*, 1>($($arg),*),
+            2 => $method::<$($generic),*, 2>($($arg),*),
+            3 => $method::<$($generic),*, 3>($($arg),*),
+            4 => $method::<$($generic),*, 4>($($arg),*),
+            5 => $method::<$($generic),*, 5>($($arg),*),
+            6 => $method::<$($generic),*, 6>($($arg),*),
+            7 => $method::<$($generic),*, 7>($($arg),*),
+            8 => $method::<$($generic),*, 8>($($arg),*),
+            channels => Err(PicScaleError::UnsupportedChannelsCount(channels)),
+        }
+    };
+}
+
+macro_rules! impl_plan_execution {
+    ($ty:ty, $($variant:ident => $method:ident, $transformed:ident<$($generic:ty),*>),+) => {
+        impl ResizePlan<$ty> {
+            /// Resizes tightly packed image, allocating intermediate storage if required
+            ///
+            /// # Arguments
+            ///
+            /// * `source`: Source image, must be at least `width * height * channels` elements
+            /// * `destination`: Destination image, must be at least `width * height * channels` elements
+            ///
+            pub fn resize(&self, source: &[$ty], destination: &mut [$ty]) -> Result<(), PicScaleError> {
+                self.resize_with_scratch(source, destination, &mut Vec::new())
+            }
+
+            /// Resizes tightly packed image, keeping intermediate pass in `scratch`
+            ///
+            /// # Arguments
+            ///
+            /// * `source`: Source image, must be at least `width * height * channels` elements
+            /// * `destination`: Destination image, must be at least `width * height * channels` elements
+            /// * `scratch`: Reusable storage for the intermediate pass, may be empty
+            ///
+            pub fn resize_with_scratch(
+                &self,
+                source: &[$ty],
+                destination: &mut [$ty],
+                scratch: &mut Vec<$ty>,
+            ) -> Result<(), PicScaleError> {
+                let channels = self.layout.channels();
+                self.resize_with_stride(
+                    source,
+                    self.source_size.width * channels,
+                    destination,
+                    self.destination_size.width * channels,
+                    scratch,
+                )
+            }
+
+            /// Resizes image with explicit row strides
+            ///
+            /// Padding after the last row is not required, so images must hold at least
+            /// `stride * (height - 1) + width * channels` elements.
+            /// Padding and elements after the last row of destination are never written.
+            ///
+            /// # Arguments
+            ///
+            /// * `source`: Source image
+            /// * `source_stride`: Source row length in elements, must be at least `width * channels`
+            /// * `destination`: Destination image
+            /// * `destination_stride`: Destination row length in elements, must be at least `width * channels`
+            /// * `scratch`: Reusable storage for the intermediate pass, may be empty
+            ///
+            pub fn resize_with_stride(
+                &self,
+                source: &[$ty],
+                source_stride: usize,
+                destination: &mut [$ty],
+                destination_stride: usize,
+                scratch: &mut Vec<$ty>,
+            ) -> Result<(), PicScaleError> {
+                let premultiply = self.needs_premultiplication(source, source_stride)?;
+                let executor = self
+                    .workers
+                    .resize_executor(self.source_size, self.destination_size);
+                match &self.weights {
+                    PlanWeights::Ewa(weights) => dispatch_layout!(
+                        self.layout,
+                        resize_ewa,
+                        $ty;
+                        source,
+                        source_stride,
+                        self.source_size,
+                        destination,
+                        destination_stride,
+                        self.destination_size,
+                        self.bit_depth,
+                        weights,
+                        premultiply,
+                        &executor,
+                    ),
+                    $(PlanWeights::$variant(weights) => match self.layout {
+                        ChannelLayout::PlaneWithAlpha if premultiply => $transformed::<$ty, $($generic),*, 2>(
+                            source,
+                            source_stride,
+                            self.source_size,
+                            destination,
+                            destination_stride,
+                            self.destination_size,
+                            self.bit_depth,
+                            weights,
+                            &AlphaTransform::<2> {
+                                bit_depth: self.bit_depth,
+                            },
+                            scratch,
+                            &executor,
+                        ),
+                        ChannelLayout::Rgba if premultiply => $transformed::<$ty, $($generic),*, 4>(
+                            source,
+                            source_stride,
+                            self.source_size,
+                            destination,
+                            destination_stride,
+                            self.destination_size,
+                            self.bit_depth,
+                            weights,
+                            &AlphaTransform::<4> {
+                                bit_depth: self.bit_depth,
+                            },
+                            scratch,
+                            &executor,
+                        ),
+                        _ => dispatch_layout!(
+                            self.layout,
+                            $method,
+                            $($generic),*;
+                            source,
+                            source_stride,
+                            self.source_size,
+                            destination,
+                            destination_stride,
+                            self.destination_size,
+                            self.bit_depth,
+                            weights,
+                            scratch,
+                            &executor,
+                        ),
+                    },)+
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!("Plan weights are always created for its data type"),
+                }
+            }
+        }
+    };
+}
+
+impl ResizePlan<u8> {
+    /// Creates plan for 8 bit-depth images
+    ///
+    /// This is using integral approximations, same as [crate::resize_rgba8] does.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+    ///
+    pub fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        resampling_function: ResamplingFunction,
+    ) -> Result<ResizePlan<u8>, PicScaleError> {
+        ResizePlan::<u8>::with_options(
+            source_size,
+            destination_size,
+            layout,
+            ResizeOptions::new(resampling_function),
+        )
+    }
+
+    /// Creates plan for 8 bit-depth images with options
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `options`: Resizing options, see [ResizeOptions] for more info
+    ///
+    pub fn with_options(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        options: ResizeOptions,
+    ) -> Result<ResizePlan<u8>, PicScaleError> {
+        check_plan_sizes(source_size, destination_size, layout, &options)?;
+        let weights = PlanWeights::new(source_size, destination_size, &options, || {
+            ResizeWeights::fixed_point(source_size, destination_size, &options)
+                .map(PlanWeights::FixedPoint)
+        })?;
+        Ok(ResizePlan::with_weights(
+            source_size,
+            destination_size,
+            layout,
+            8,
+            options,
+            weights,
+        ))
+    }
+}
+
+impl ResizePlan<u16> {
+    /// Creates plan for high bit-depth images
+    ///
+    /// For 10 and 12 bit-depth integral approximations are used, others are processed
+    /// in `f32`, same as [crate::resize_rgba16] does.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `bit_depth`: Bit-depth of the image, must be in range 1..=16
+    /// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+    ///
+    pub fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        bit_depth: u32,
+        resampling_function: ResamplingFunction,
+    ) -> Result<ResizePlan<u16>, PicScaleError> {
+        ResizePlan::<u16>::with_options(
+            source_size,
+            destination_size,
+            layout,
+            bit_depth,
+            ResizeOptions::new(resampling_function),
+        )
+    }
+
+    /// Creates plan for high bit-depth images with options
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `bit_depth`: Bit-depth of the image, must be in range 1..=16
+    /// * `options`: Resizing options, see [ResizeOptions] for more info
+    ///
+    pub fn with_options(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        bit_depth: u32,
+        options: ResizeOptions,
+    ) -> Result<ResizePlan<u16>, PicScaleError> {
+        check_bit_depth(bit_depth)?;
+        check_plan_sizes(source_size, destination_size, layout, &options)?;
+        let weights = PlanWeights::new(source_size, destination_size, &options, || {
+            if bit_depth == 10 || bit_depth == 12 {
+                ResizeWeights::fixed_point(source_size, destination_size, &options)
+                    .map(PlanWeights::FixedPoint)
+            } else {
+                ResizeWeights::floating_point(source_size, destination_size, &options)
+                    .map(PlanWeights::FloatingPoint)
+            }
+        })?;
+        Ok(ResizePlan::with_weights(
+            source_size,
+            destination_size,
+            layout,
+            bit_depth,
+            options,
+            weights,
+        ))
+    }
+}
+
+impl ResizePlan<f32> {
+    /// Creates plan for `f32` images
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+    ///
+    pub fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        resampling_function: ResamplingFunction,
+    ) -> Result<ResizePlan<f32>, PicScaleError> {
+        ResizePlan::<f32>::with_options(
+            source_size,
+            destination_size,
+            layout,
+            ResizeOptions::new(resampling_function),
+        )
+    }
+
+    /// Creates plan for `f32` images with options
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `options`: Resizing options, see [ResizeOptions] for more info
+    ///
+    pub fn with_options(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        options: ResizeOptions,
+    ) -> Result<ResizePlan<f32>, PicScaleError> {
+        check_plan_sizes(source_size, destination_size, layout, &options)?;
+        let weights = PlanWeights::new(source_size, destination_size, &options, || {
+            ResizeWeights::floating_point(source_size, destination_size, &options)
+                .map(PlanWeights::FloatingPoint)
+        })?;
+        Ok(ResizePlan::with_weights(
+            source_size,
+            destination_size,
+            layout,
+            8,
+            options,
+            weights,
+        ))
+    }
+}
+
+#[cfg(feature = "half")]
+impl ResizePlan<half::f16> {
+    /// Creates plan for `f16` images
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+    ///
+    pub fn new(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        resampling_function: ResamplingFunction,
+    ) -> Result<ResizePlan<half::f16>, PicScaleError> {
+        ResizePlan::<half::f16>::with_options(
+            source_size,
+            destination_size,
+            layout,
+            ResizeOptions::new(resampling_function),
+        )
+    }
+
+    /// Creates plan for `f16` images with options
+    ///
+    /// # Arguments
+    ///
+    /// * `source_size`: Source image size
+    /// * `destination_size`: Destination image size
+    /// * `layout`: Channel layout, see [ChannelLayout] for more info
+    /// * `options`: Resizing options, see [ResizeOptions] for more info
+    ///
+    pub fn with_options(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        layout: ChannelLayout,
+        options: ResizeOptions,
+    ) -> Result<ResizePlan<half::f16>, PicScaleError> {
+        check_plan_sizes(source_size, destination_size, layout, &options)?;
+        let weights = PlanWeights::new(source_size, destination_size, &options, || {
+            ResizeWeights::floating_point(source_size, destination_size, &options)
+                .map(PlanWeights::FloatingPoint)
+        })?;
+        Ok(ResizePlan::with_weights(
+            source_size,
+            destination_size,
+            layout,
+            8,
+            options,
+            weights,
+        ))
+    }
+}
+
+impl_plan_execution!(
+    u8,
+    FixedPoint => resize_fixed_point_with_weights, resize_fixed_point_transformed<u8, i32>
+);
+impl_plan_execution!(
+    u16,
+    FixedPoint => resize_fixed_point_with_weights, resize_fixed_point_transformed<u16, i32>,
+    FloatingPoint => resize_floating_point_with_weights,
+        resize_floating_point_transformed<u16, f32, f32>
+);
+impl_plan_execution!(
+    f32,
+    FloatingPoint => resize_floating_point_with_weights,
+        resize_floating_point_transformed<f32, f32, f32>
+);
+#[cfg(feature = "half")]
+impl_plan_execution!(
+    half::f16,
+    FloatingPoint => resize_floating_point_with_weights,
+        resize_floating_point_transformed<half::f16, f32, f32>
+);
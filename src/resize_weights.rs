@@ -0,0 +1,246 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::compute_weights::generate_weights;
+use crate::definitions::PRECISION;
+use crate::error::PicScaleError;
+use crate::filter_weights::FilterWeights;
+use crate::image_size::ImageSize;
+use crate::integer_ratio::IntegerRatio;
+use crate::math::{ConstPI, ConstSqrt2, Jinc};
+use crate::resize_nearest::nearest_indices;
+use crate::resize_options::{PassOrder, ResizeOptions};
+use crate::sampler::ResamplingFunction;
+use crate::source_rect::AxisMapping;
+use num_traits::{AsPrimitive, Float, Signed};
+use std::fmt::Debug;
+use std::ops::{AddAssign, Div, MulAssign, Neg};
+
+/// Everything what is required to execute resize between two fixed sizes
+#[derive(Debug, Clone)]
+pub(crate) enum ResizeWeights<W> {
+    /// Sizes are the same, image is just copied
+    Copy,
+    /// Nearest neighbour sampling, source column and row for each destination pixel
+    Nearest {
+        columns: Vec<Option<usize>>,
+        rows: Vec<Option<usize>>,
+    },
+    /// Separable convolution, pass is `None` when its dimension is not changing
+    Convolution {
+        vertical: Option<FilterWeights<W>>,
+        horizontal: Option<FilterWeights<W>>,
+        /// Horizontal pass is executed before vertical, meaningful only when both are present
+        horizontal_first: bool,
+    },
+    /// Sizes are in integral ratio and fast path is taken when the whole image is available,
+    /// `general` weights are executed when rows are streamed or converted on the fly
+    IntegerRatio {
+        ratio: IntegerRatio,
+        general: Box<ResizeWeights<W>>,
+    },
+}
+
+impl<W> ResizeWeights<W> {
+    /// Weights without integral ratio fast path
+    pub(crate) fn general(&self) -> &ResizeWeights<W> {
+        match self {
+            ResizeWeights::IntegerRatio { general, .. } => general,
+            weights => weights,
+        }
+    }
+
+    fn generate(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+        generator: impl Fn(usize, usize, Option<AxisMapping>) -> Result<FilterWeights<W>, PicScaleError>,
+    ) -> Result<ResizeWeights<W>, PicScaleError> {
+        let (horizontal_mapping, vertical_mapping) =
+            options.axis_mappings(destination_size).unzip();
+        if source_size == destination_size && horizontal_mapping.is_none() {
+            return Ok(ResizeWeights::Copy);
+        }
+        // Blur, taper and anti-ringing reshape the box filter of area downscale
+        let is_plain_area = !options.is_kernel_shaped() && options.anti_ringing == 0.;
+        let ratio = match (horizontal_mapping, options.resampling_function) {
+            (None, ResamplingFunction::Area) if is_plain_area => {
+                IntegerRatio::factors(source_size, destination_size)
+                    .map(|(x_factor, y_factor)| IntegerRatio::Average { x_factor, y_factor })
+            }
+            (None, ResamplingFunction::Nearest) => {
+                IntegerRatio::factors(destination_size, source_size)
+                    .map(|(x_factor, y_factor)| IntegerRatio::Replicate { x_factor, y_factor })
+            }
+            _ => None,
+        };
+        let general = ResizeWeights::generate_general(
+            source_size,
+            destination_size,
+            options,
+            horizontal_mapping,
+            vertical_mapping,
+            generator,
+        )?;
+        Ok(match ratio {
+            Some(ratio) => ResizeWeights::IntegerRatio {
+                ratio,
+                general: Box::new(general),
+            },
+            None => general,
+        })
+    }
+
+    fn generate_general(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+        horizontal_mapping: Option<AxisMapping>,
+        vertical_mapping: Option<AxisMapping>,
+        generator: impl Fn(usize, usize, Option<AxisMapping>) -> Result<FilterWeights<W>, PicScaleError>,
+    ) -> Result<ResizeWeights<W>, PicScaleError> {
+        if options.resampling_function == ResamplingFunction::Nearest {
+            let edge_mode = options.edge_mode;
+            return Ok(ResizeWeights::Nearest {
+                columns: nearest_indices(
+                    source_size.width,
+                    destination_size.width,
+                    horizontal_mapping,
+                    edge_mode,
+                ),
+                rows: nearest_indices(
+                    source_size.height,
+                    destination_size.height,
+                    vertical_mapping,
+                    edge_mode,
+                ),
+            });
+        }
+        let vertical =
+            if source_size.height != destination_size.height || vertical_mapping.is_some() {
+                Some(generator(
+                    source_size.height,
+                    destination_size.height,
+                    vertical_mapping,
+                )?)
+            } else {
+                None
+            };
+        let horizontal =
+            if source_size.width != destination_size.width || horizontal_mapping.is_some() {
+                Some(generator(
+                    source_size.width,
+                    destination_size.width,
+                    horizontal_mapping,
+                )?)
+            } else {
+                None
+            };
+        let horizontal_first = match (&vertical, &horizontal) {
+            (Some(vertical), Some(horizontal)) => match options.pass_order {
+                PassOrder::Auto => {
+                    // Multiply-accumulate count of each ordering
+                    let vertical_first_cost = destination_size.height
+                        * (source_size.width * vertical.kernel_size
+                            + destination_size.width * horizontal.kernel_size);
+                    let horizontal_first_cost = destination_size.width
+                        * (source_size.height * horizontal.kernel_size
+                            + destination_size.height * vertical.kernel_size);
+                    horizontal_first_cost < vertical_first_cost
+                }
+                PassOrder::VerticalFirst => false,
+                PassOrder::HorizontalFirst => true,
+            },
+            _ => false,
+        };
+        Ok(ResizeWeights::Convolution {
+            vertical,
+            horizontal,
+            horizontal_first,
+        })
+    }
+}
+
+impl ResizeWeights<i16> {
+    /// Generates weights and quantizes them into integral approximation
+    pub(crate) fn fixed_point(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+    ) -> Result<ResizeWeights<i16>, PicScaleError> {
+        ResizeWeights::generate(
+            source_size,
+            destination_size,
+            options,
+            |in_size, out_size, mapping| {
+                generate_weights::<f32>(in_size, out_size, mapping, options)
+                    .map(|weights| weights.numerical_approximation_i16::<PRECISION>(0))
+            },
+        )
+    }
+}
+
+impl<F> ResizeWeights<F>
+where
+    F: Copy
+        + Neg
+        + Signed
+        + Float
+        + 'static
+        + ConstPI
+        + MulAssign<F>
+        + AddAssign<F>
+        + AsPrimitive<f64>
+        + AsPrimitive<i64>
+        + AsPrimitive<usize>
+        + Jinc<F>
+        + ConstSqrt2
+        + Default
+        + AsPrimitive<i32>
+        + Div<F, Output = F>
+        + Debug,
+    f32: AsPrimitive<F>,
+    f64: AsPrimitive<F>,
+    i64: AsPrimitive<F>,
+    i32: AsPrimitive<F>,
+    usize: AsPrimitive<F>,
+{
+    /// Generates floating point weights
+    pub(crate) fn floating_point(
+        source_size: ImageSize,
+        destination_size: ImageSize,
+        options: &ResizeOptions,
+    ) -> Result<ResizeWeights<F>, PicScaleError> {
+        ResizeWeights::generate(
+            source_size,
+            destination_size,
+            options,
+            |in_size, out_size, mapping| generate_weights::<F>(in_size, out_size, mapping, options),
+        )
+    }
+}
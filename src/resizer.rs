@@ -28,7 +28,10 @@
  */
 use crate::resize_fixed_point::resize_fixed_point;
 use crate::resize_floating_point::resize_floating_point;
-use crate::{ImageSize, ResamplingFunction};
+use crate::resize_linear::resize_linear_into;
+use crate::threading::Workers;
+use crate::validation::{check_bit_depth, check_layout, packed_stride};
+use crate::{ImageSize, PicScaleError, ResamplingFunction, TransferFunction};
 
 /// Performs resizing on RGBA 8 bit-depth image
 ///
@@ -36,6 +39,8 @@ use crate::{ImageSize, ResamplingFunction};
 /// use [crate::premultiply_rgba8] before do scaling, and [crate::unpremultiply_rgba8]
 /// after.
 ///
+/// Alternatively [crate::ResizePlan] with [crate::PremultiplyMode::Auto] does it on the fly.
+///
 /// Any content preferred to be in linear colorspace or perceptual before resizing,
 /// consider using [crate::linear_to_gamma_image] and [crate::image_to_linear] if required,
 /// otherwise results will degrade.
@@ -63,13 +68,16 @@ pub fn resize_rgba8(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, PicScaleError> {
     resize_fixed_point::<u8, i32, 4>(
         source,
+        packed_stride::<4>(source_size)?,
         source_size,
+        packed_stride::<4>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -102,13 +110,16 @@ pub fn resize_rgb8(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, PicScaleError> {
     resize_fixed_point::<u8, i32, 3>(
         source,
+        packed_stride::<3>(source_size)?,
         source_size,
+        packed_stride::<3>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -141,13 +152,16 @@ pub fn resize_plane8(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, PicScaleError> {
     resize_fixed_point::<u8, i32, 1>(
         source,
+        packed_stride::<1>(source_size)?,
         source_size,
+        packed_stride::<1>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -180,13 +194,16 @@ pub fn resize_plane8_with_alpha(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, PicScaleError> {
     resize_fixed_point::<u8, i32, 2>(
         source,
+        packed_stride::<2>(source_size)?,
         source_size,
+        packed_stride::<2>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -196,6 +213,8 @@ pub fn resize_plane8_with_alpha(
 /// use [crate::premultiply_rgba16] before do scaling, and [crate::unpremultiply_rgba16]
 /// after.
 ///
+/// Alternatively [crate::ResizePlan] with [crate::PremultiplyMode::Auto] does it on the fly.
+///
 /// Any content preferred to be in linear colorspace or perceptual before resizing,
 /// consider using [crate::linear16_to_gamma_image16] and [crate::image16_to_linear16] if required,
 /// otherwise results will degrade.
@@ -226,25 +245,29 @@ pub fn resize_rgba16(
     destination_size: ImageSize,
     bit_depth: u32,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u16>, String> {
-    if bit_depth > 16 || bit_depth == 0 {
-        return Err("Bit depth cannot be greater than 16 and not a zero".to_string());
-    }
+) -> Result<Vec<u16>, PicScaleError> {
+    check_bit_depth(bit_depth)?;
     if bit_depth == 10 || bit_depth == 12 {
         resize_fixed_point::<u16, i32, 4>(
             source,
+            packed_stride::<4>(source_size)?,
             source_size,
+            packed_stride::<4>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     } else {
         resize_floating_point::<u16, f32, f32, 4>(
             source,
+            packed_stride::<4>(source_size)?,
             source_size,
+            packed_stride::<4>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     }
 }
@@ -281,25 +304,29 @@ pub fn resize_rgb16(
     destination_size: ImageSize,
     bit_depth: u32,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u16>, String> {
-    if bit_depth > 16 || bit_depth == 0 {
-        return Err("Bit depth cannot be greater than 16 and not a zero".to_string());
-    }
+) -> Result<Vec<u16>, PicScaleError> {
+    check_bit_depth(bit_depth)?;
     if bit_depth == 10 || bit_depth == 12 {
         resize_fixed_point::<u16, i32, 3>(
             source,
+            packed_stride::<3>(source_size)?,
             source_size,
+            packed_stride::<3>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     } else {
         resize_floating_point::<u16, f32, f32, 3>(
             source,
+            packed_stride::<3>(source_size)?,
             source_size,
+            packed_stride::<3>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     }
 }
@@ -336,25 +363,29 @@ pub fn resize_plane16(
     destination_size: ImageSize,
     bit_depth: u32,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u16>, String> {
-    if bit_depth > 16 || bit_depth == 0 {
-        return Err("Bit depth cannot be greater than 16 and not a zero".to_string());
-    }
+) -> Result<Vec<u16>, PicScaleError> {
+    check_bit_depth(bit_depth)?;
     if bit_depth == 10 || bit_depth == 12 {
         resize_fixed_point::<u16, i32, 1>(
             source,
+            packed_stride::<1>(source_size)?,
             source_size,
+            packed_stride::<1>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     } else {
         resize_floating_point::<u16, f32, f32, 1>(
             source,
+            packed_stride::<1>(source_size)?,
             source_size,
+            packed_stride::<1>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     }
 }
@@ -391,25 +422,29 @@ pub fn resize_plane16_with_alpha(
     destination_size: ImageSize,
     bit_depth: u32,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<u16>, String> {
-    if bit_depth > 16 || bit_depth == 0 {
-        return Err("Bit depth cannot be greater than 16 and not a zero".to_string());
-    }
+) -> Result<Vec<u16>, PicScaleError> {
+    check_bit_depth(bit_depth)?;
     if bit_depth == 10 || bit_depth == 12 {
         resize_fixed_point::<u16, i32, 2>(
             source,
+            packed_stride::<2>(source_size)?,
             source_size,
+            packed_stride::<2>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     } else {
         resize_floating_point::<u16, f32, f32, 2>(
             source,
+            packed_stride::<2>(source_size)?,
             source_size,
+            packed_stride::<2>(destination_size)?,
             destination_size,
             bit_depth,
             resampling_function,
+            &Workers::default(),
         )
     }
 }
@@ -420,6 +455,8 @@ pub fn resize_plane16_with_alpha(
 /// use [crate::premultiply_rgba_f32] before do scaling, and [crate::unpremultiply_rgba_f32]
 /// after.
 ///
+/// Alternatively [crate::ResizePlan] with [crate::PremultiplyMode::Auto] does it on the fly.
+///
 /// Any content preferred to be in linear colorspace or perceptual before resizing,
 /// consider using [linear_f32_to_gamma_image_f32] and [image_f32_to_linear_f32] if required,
 /// otherwise results will degrade.
@@ -444,13 +481,16 @@ pub fn resize_rgba_f32(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<f32>, String> {
+) -> Result<Vec<f32>, PicScaleError> {
     resize_floating_point::<f32, f32, f32, 4>(
         source,
+        packed_stride::<4>(source_size)?,
         source_size,
+        packed_stride::<4>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -480,13 +520,16 @@ pub fn resize_rgb_f32(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<f32>, String> {
+) -> Result<Vec<f32>, PicScaleError> {
     resize_floating_point::<f32, f32, f32, 3>(
         source,
+        packed_stride::<3>(source_size)?,
         source_size,
+        packed_stride::<3>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -516,13 +559,16 @@ pub fn resize_plane_f32(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<f32>, String> {
+) -> Result<Vec<f32>, PicScaleError> {
     resize_floating_point::<f32, f32, f32, 1>(
         source,
+        packed_stride::<1>(source_size)?,
         source_size,
+        packed_stride::<1>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
 
@@ -552,12 +598,377 @@ pub fn resize_luma_alpha_f32(
     source_size: ImageSize,
     destination_size: ImageSize,
     resampling_function: ResamplingFunction,
-) -> Result<Vec<f32>, String> {
+) -> Result<Vec<f32>, PicScaleError> {
     resize_floating_point::<f32, f32, f32, 2>(
         source,
+        packed_stride::<2>(source_size)?,
+        source_size,
+        packed_stride::<2>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
+
+/// Performs resizing on interleaved 8 bit-depth image with any channels count
+///
+/// Every channel is resampled independently, so this suits CMYK(A), multispectral
+/// bands or feature maps where none of channels is alpha. If one of channels is alpha
+/// it must be associated with the others before scaling.
+///
+/// Images with up to [crate::ChannelLayout::MAX_CHANNELS] channels might be resized
+/// with options, threading or padded rows by [crate::ResizePlan]
+/// with [crate::ChannelLayout::Interleaved].
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Generics
+///
+/// * `CHANNELS`: Channels count of the image, must be at least 1
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{resize_interleaved8, ImageSize, ResamplingFunction};
+///
+/// let cmyka = vec![127u8; 16 * 16 * 5];
+/// let resized = resize_interleaved8::<5>(
+///     &cmyka,
+///     ImageSize::new(16, 16),
+///     ImageSize::new(8, 8),
+///     ResamplingFunction::Lanczos3,
+/// )
+/// .unwrap();
+/// assert_eq!(resized.len(), 8 * 8 * 5);
+/// ```
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+/// This is using integral approximations, if more precise results are required use direct call
+/// to [resize_floating_point::<u8, f32, f32, CHANNELS>]
+///
+pub fn resize_interleaved8<const CHANNELS: usize>(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    resize_fixed_point::<u8, i32, CHANNELS>(
+        source,
+        packed_stride::<CHANNELS>(source_size)?,
+        source_size,
+        packed_stride::<CHANNELS>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
+
+/// Performs resizing on interleaved 8-16 bit-depth image with any channels count
+///
+/// Every channel is resampled independently, see [resize_interleaved8] for more info.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `bit_depth`: Bit-depth of the image
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Generics
+///
+/// * `CHANNELS`: Channels count of the image, must be at least 1
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+/// This is using integral approximations for images 10, 12 bit depth,
+/// if more precise results are required use direct call
+/// to [resize_floating_point::<u16, f32, f32, CHANNELS>]
+///
+pub fn resize_interleaved16<const CHANNELS: usize>(
+    source: &[u16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u16>, PicScaleError> {
+    check_bit_depth(bit_depth)?;
+    if bit_depth == 10 || bit_depth == 12 {
+        resize_fixed_point::<u16, i32, CHANNELS>(
+            source,
+            packed_stride::<CHANNELS>(source_size)?,
+            source_size,
+            packed_stride::<CHANNELS>(destination_size)?,
+            destination_size,
+            bit_depth,
+            resampling_function,
+            &Workers::default(),
+        )
+    } else {
+        resize_floating_point::<u16, f32, f32, CHANNELS>(
+            source,
+            packed_stride::<CHANNELS>(source_size)?,
+            source_size,
+            packed_stride::<CHANNELS>(destination_size)?,
+            destination_size,
+            bit_depth,
+            resampling_function,
+            &Workers::default(),
+        )
+    }
+}
+
+/// Performs resizing on interleaved f32 image with any channels count
+///
+/// Every channel is resampled independently, see [resize_interleaved8] for more info.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Generics
+///
+/// * `CHANNELS`: Channels count of the image, must be at least 1
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_interleaved_f32<const CHANNELS: usize>(
+    source: &[f32],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<f32>, PicScaleError> {
+    resize_floating_point::<f32, f32, f32, CHANNELS>(
+        source,
+        packed_stride::<CHANNELS>(source_size)?,
         source_size,
+        packed_stride::<CHANNELS>(destination_size)?,
         destination_size,
         8,
         resampling_function,
+        &Workers::default(),
     )
 }
+
+/// Performs resizing on RGBA 8 bit-depth image in linear light
+///
+/// Same as [resize_rgba8], but image is linearized with `transfer_function` into
+/// 12 bit-depth intermediate during the first pass and encoded back on the last one.
+/// This avoids banding produced by [crate::image_to_linear] and [crate::linear_to_gamma_image]
+/// on 8-bit storage, and does not require separate passes over the full image.
+///
+/// Alpha is not linearized. When it is not constant, it is associated with linear color
+/// on the fly and dissociated before encoding, so image must not be premultiplied beforehand.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+/// * `transfer_function`: Transfer function of the image, see [TransferFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_rgba8_linear(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    transfer_function: TransferFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    let destination_stride = packed_stride::<4>(destination_size)?;
+    check_layout::<4>(destination_stride, destination_size)?;
+    let mut destination = vec![0u8; destination_stride * destination_size.height];
+    resize_linear_into::<4>(
+        source,
+        packed_stride::<4>(source_size)?,
+        source_size,
+        &mut destination,
+        destination_stride,
+        destination_size,
+        resampling_function,
+        transfer_function,
+        &mut Vec::new(),
+    )?;
+    Ok(destination)
+}
+
+/// Performs resizing on RGB 8 bit-depth image in linear light
+///
+/// Same as [resize_rgb8], but image is linearized with `transfer_function` into
+/// 12 bit-depth intermediate during the first pass and encoded back on the last one.
+/// This avoids banding produced by [crate::image_to_linear] and [crate::linear_to_gamma_image]
+/// on 8-bit storage, and does not require separate passes over the full image.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+/// * `transfer_function`: Transfer function of the image, see [TransferFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_rgb8_linear(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    transfer_function: TransferFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    let destination_stride = packed_stride::<3>(destination_size)?;
+    check_layout::<3>(destination_stride, destination_size)?;
+    let mut destination = vec![0u8; destination_stride * destination_size.height];
+    resize_linear_into::<3>(
+        source,
+        packed_stride::<3>(source_size)?,
+        source_size,
+        &mut destination,
+        destination_stride,
+        destination_size,
+        resampling_function,
+        transfer_function,
+        &mut Vec::new(),
+    )?;
+    Ok(destination)
+}
+
+/// Performs resizing on planar 8 bit-depth image in linear light
+///
+/// Same as [resize_plane8], but image is linearized with `transfer_function` into
+/// 12 bit-depth intermediate during the first pass and encoded back on the last one.
+/// This avoids banding produced by [crate::image_to_linear] and [crate::linear_to_gamma_image]
+/// on 8-bit storage, and does not require separate passes over the full image.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+/// * `transfer_function`: Transfer function of the image, see [TransferFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_plane8_linear(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    transfer_function: TransferFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    let destination_stride = packed_stride::<1>(destination_size)?;
+    check_layout::<1>(destination_stride, destination_size)?;
+    let mut destination = vec![0u8; destination_stride * destination_size.height];
+    resize_linear_into::<1>(
+        source,
+        packed_stride::<1>(source_size)?,
+        source_size,
+        &mut destination,
+        destination_stride,
+        destination_size,
+        resampling_function,
+        transfer_function,
+        &mut Vec::new(),
+    )?;
+    Ok(destination)
+}
+
+/// Performs resizing on planar with alpha 8 bit-depth image in linear light
+///
+/// Same as [resize_plane8_with_alpha], but image is linearized with `transfer_function` into
+/// 12 bit-depth intermediate during the first pass and encoded back on the last one.
+/// This avoids banding produced by [crate::image_to_linear] and [crate::linear_to_gamma_image]
+/// on 8-bit storage, and does not require separate passes over the full image.
+///
+/// Alpha is not linearized. When it is not constant, it is associated with linear color
+/// on the fly and dissociated before encoding, so image must not be premultiplied beforehand.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+/// * `transfer_function`: Transfer function of the image, see [TransferFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_plane8_with_alpha_linear(
+    source: &[u8],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+    transfer_function: TransferFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    let destination_stride = packed_stride::<2>(destination_size)?;
+    check_layout::<2>(destination_stride, destination_size)?;
+    let mut destination = vec![0u8; destination_stride * destination_size.height];
+    resize_linear_into::<2>(
+        source,
+        packed_stride::<2>(source_size)?,
+        source_size,
+        &mut destination,
+        destination_stride,
+        destination_size,
+        resampling_function,
+        transfer_function,
+        &mut Vec::new(),
+    )?;
+    Ok(destination)
+}
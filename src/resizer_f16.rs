@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::resize_floating_point::resize_floating_point;
+use crate::threading::Workers;
+use crate::validation::packed_stride;
+use crate::{ImageSize, PicScaleError, ResamplingFunction};
+
+/// Performs resizing on RGBA f16 image
+///
+/// To perform scaling on the image alpha must be associated first
+/// use [crate::premultiply_rgba_f16] before do scaling, and [crate::unpremultiply_rgba_f16]
+/// after.
+///
+/// Alternatively [crate::ResizePlan] with [crate::PremultiplyMode::Auto] does it on the fly.
+///
+/// Any content preferred to be in linear colorspace or perceptual before resizing,
+/// otherwise results will degrade.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_rgba_f16(
+    source: &[half::f16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<half::f16>, PicScaleError> {
+    resize_floating_point::<half::f16, f32, f32, 4>(
+        source,
+        packed_stride::<4>(source_size)?,
+        source_size,
+        packed_stride::<4>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
+
+/// Performs resizing on RGB f16 image
+///
+/// Any content preferred to be in linear colorspace or perceptual before resizing,
+/// otherwise results will degrade.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_rgb_f16(
+    source: &[half::f16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<half::f16>, PicScaleError> {
+    resize_floating_point::<half::f16, f32, f32, 3>(
+        source,
+        packed_stride::<3>(source_size)?,
+        source_size,
+        packed_stride::<3>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
+
+/// Performs resizing on planar f16 image
+///
+/// Any content preferred to be in linear colorspace or perceptual before resizing,
+/// otherwise results will degrade.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_plane_f16(
+    source: &[half::f16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<half::f16>, PicScaleError> {
+    resize_floating_point::<half::f16, f32, f32, 1>(
+        source,
+        packed_stride::<1>(source_size)?,
+        source_size,
+        packed_stride::<1>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
+
+/// Performs resizing on Luma-alpha f16 image
+///
+/// Any content preferred to be in linear colorspace or perceptual before resizing,
+/// otherwise results will degrade.
+///
+/// # Arguments
+///
+/// * `source`: Source image
+/// * `source_size`: Source image size
+/// * `destination_size`: Destination image size
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Resized image, this bounds always match destination size
+///
+/// # Limitations
+///
+/// The contract `width * channels < usize::MAX` must be always satisfied and cannot be broken
+///
+pub fn resize_luma_alpha_f16(
+    source: &[half::f16],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<half::f16>, PicScaleError> {
+    resize_floating_point::<half::f16, f32, f32, 2>(
+        source,
+        packed_stride::<2>(source_size)?,
+        source_size,
+        packed_stride::<2>(destination_size)?,
+        destination_size,
+        8,
+        resampling_function,
+        &Workers::default(),
+    )
+}
@@ -0,0 +1,640 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::validation::check_bit_depth;
+use crate::yuv::{
+    chroma_resize_options, ChromaFormat, ChromaSiting, YuvBitPacking, YuvPlanarImage,
+    YuvPlanarImageMut, YuvSemiPlanarImage, YuvSemiPlanarImageMut, YuvSubsampling,
+};
+use crate::{
+    ChannelLayout, ImageSize, PicScaleError, ResamplingFunction, ResizeOptions, ResizePlan,
+};
+
+/// Creates plans for luma and chroma planes of the frame
+fn yuv_plans<T>(
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    chroma_layout: ChannelLayout,
+    resampling_function: ResamplingFunction,
+    make_plan: impl Fn(
+        ImageSize,
+        ImageSize,
+        ChannelLayout,
+        ResizeOptions,
+    ) -> Result<ResizePlan<T>, PicScaleError>,
+) -> Result<(ResizePlan<T>, ResizePlan<T>), PicScaleError> {
+    let options = ResizeOptions::new(resampling_function);
+    let format = ChromaFormat::new(subsampling, siting);
+    let luma_plan = make_plan(source_size, destination_size, ChannelLayout::Plane, options)?;
+    let chroma_plan = make_plan(
+        subsampling.chroma_size(source_size),
+        subsampling.chroma_size(destination_size),
+        chroma_layout,
+        chroma_resize_options(source_size, format, destination_size, format, options),
+    )?;
+    Ok((luma_plan, chroma_plan))
+}
+
+/// Resizes luma and both chroma planes, creating plans with `make_plan`
+fn resize_yuv_planes<T>(
+    source: &YuvPlanarImage<T>,
+    destination: &mut YuvPlanarImageMut<T>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    resampling_function: ResamplingFunction,
+    make_plan: impl Fn(
+        ImageSize,
+        ImageSize,
+        ChannelLayout,
+        ResizeOptions,
+    ) -> Result<ResizePlan<T>, PicScaleError>,
+    execute: impl Fn(
+        &ResizePlan<T>,
+        &[T],
+        usize,
+        &mut [T],
+        usize,
+        &mut Vec<T>,
+    ) -> Result<(), PicScaleError>,
+) -> Result<(), PicScaleError> {
+    let (luma_plan, chroma_plan) = yuv_plans(
+        source.size,
+        destination.size,
+        subsampling,
+        siting,
+        ChannelLayout::Plane,
+        resampling_function,
+        make_plan,
+    )?;
+    let mut scratch = Vec::new();
+    execute(
+        &luma_plan,
+        source.y_plane,
+        source.y_stride,
+        destination.y_plane,
+        destination.y_stride,
+        &mut scratch,
+    )?;
+    execute(
+        &chroma_plan,
+        source.u_plane,
+        source.u_stride,
+        destination.u_plane,
+        destination.u_stride,
+        &mut scratch,
+    )?;
+    execute(
+        &chroma_plan,
+        source.v_plane,
+        source.v_stride,
+        destination.v_plane,
+        destination.v_stride,
+        &mut scratch,
+    )
+}
+
+/// Resizes luma and interleaved chroma planes, creating plans with `make_plan`
+fn resize_yuv_semi_planar<T>(
+    source: &YuvSemiPlanarImage<T>,
+    destination: &mut YuvSemiPlanarImageMut<T>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    resampling_function: ResamplingFunction,
+    make_plan: impl Fn(
+        ImageSize,
+        ImageSize,
+        ChannelLayout,
+        ResizeOptions,
+    ) -> Result<ResizePlan<T>, PicScaleError>,
+    execute: impl Fn(
+        &ResizePlan<T>,
+        &[T],
+        usize,
+        &mut [T],
+        usize,
+        &mut Vec<T>,
+    ) -> Result<(), PicScaleError>,
+) -> Result<(), PicScaleError> {
+    // Chroma pairs are never alpha, plans are created without premultiplication
+    let (luma_plan, chroma_plan) = yuv_plans(
+        source.size,
+        destination.size,
+        subsampling,
+        siting,
+        ChannelLayout::PlaneWithAlpha,
+        resampling_function,
+        make_plan,
+    )?;
+    let mut scratch = Vec::new();
+    execute(
+        &luma_plan,
+        source.y_plane,
+        source.y_stride,
+        destination.y_plane,
+        destination.y_stride,
+        &mut scratch,
+    )?;
+    execute(
+        &chroma_plan,
+        source.uv_plane,
+        source.uv_stride,
+        destination.uv_plane,
+        destination.uv_stride,
+        &mut scratch,
+    )
+}
+
+/// Rounds 16 bit-depth samples to the nearest MSB-aligned `bit_depth` value
+fn align_msb(plane: &mut [u16], stride: usize, size: ImageSize, channels: usize, bit_depth: u32) {
+    let shift = 16 - bit_depth;
+    if shift == 0 {
+        return;
+    }
+    let max_value = (1u32 << bit_depth) - 1;
+    let half = 1u32 << (shift - 1);
+    let row_length = size.width * channels;
+    for row in plane.chunks_mut(stride).take(size.height) {
+        for v in row[..row_length].iter_mut() {
+            let rounded = ((*v as u32 + half) >> shift).min(max_value);
+            *v = (rounded << shift) as u16;
+        }
+    }
+}
+
+/// Performs resizing on planar YUV 8 bit-depth image
+///
+/// Chroma planes are resampled at the positions given by `siting`, so chroma stays aligned
+/// with luma after resizing, instead of shifting by a part of the pixel
+/// as separate calls to [crate::resize_plane8] would do.
+///
+/// # Arguments
+///
+/// * `source`: Source image, chroma planes must be sized according to `subsampling`
+/// * `destination`: Destination image, chroma planes must be sized according to `subsampling`
+/// * `subsampling`: Chroma subsampling of both images, see [YuvSubsampling] for more info
+/// * `siting`: Chroma sample position of both images, see [ChromaSiting] for more info
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{
+///     resize_yuv8, ChromaSiting, ImageSize, ResamplingFunction, YuvPlanarImage,
+///     YuvPlanarImageMut, YuvSubsampling,
+/// };
+///
+/// let y = vec![16u8; 64 * 48];
+/// let u = vec![128u8; 32 * 24];
+/// let v = vec![128u8; 32 * 24];
+/// let source = YuvPlanarImage {
+///     y_plane: &y,
+///     y_stride: 64,
+///     u_plane: &u,
+///     u_stride: 32,
+///     v_plane: &v,
+///     v_stride: 32,
+///     size: ImageSize::new(64, 48),
+/// };
+/// let mut dst_y = vec![0u8; 32 * 24];
+/// let mut dst_u = vec![0u8; 16 * 12];
+/// let mut dst_v = vec![0u8; 16 * 12];
+/// let mut destination = YuvPlanarImageMut {
+///     y_plane: &mut dst_y,
+///     y_stride: 32,
+///     u_plane: &mut dst_u,
+///     u_stride: 16,
+///     v_plane: &mut dst_v,
+///     v_stride: 16,
+///     size: ImageSize::new(32, 24),
+/// };
+/// resize_yuv8(
+///     &source,
+///     &mut destination,
+///     YuvSubsampling::Yuv420,
+///     ChromaSiting::Left,
+///     ResamplingFunction::Lanczos3,
+/// )
+/// .unwrap();
+/// ```
+///
+/// # Limitations
+///
+/// This is using integral approximations, same as [crate::resize_plane8] does.
+///
+pub fn resize_yuv8(
+    source: &YuvPlanarImage<u8>,
+    destination: &mut YuvPlanarImageMut<u8>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    resampling_function: ResamplingFunction,
+) -> Result<(), PicScaleError> {
+    resize_yuv_planes(
+        source,
+        destination,
+        subsampling,
+        siting,
+        resampling_function,
+        |source_size, destination_size, layout, options| {
+            ResizePlan::<u8>::with_options(source_size, destination_size, layout, options)
+        },
+        |plan, source, source_stride, destination, destination_stride, scratch| {
+            plan.resize_with_stride(
+                source,
+                source_stride,
+                destination,
+                destination_stride,
+                scratch,
+            )
+        },
+    )
+}
+
+/// Performs resizing on planar YUV 8-16 bit-depth image
+///
+/// Same as [resize_yuv8], but for high bit-depth images where samples are stored
+/// in the low bits of `u16`.
+///
+/// # Arguments
+///
+/// * `source`: Source image, chroma planes must be sized according to `subsampling`
+/// * `destination`: Destination image, chroma planes must be sized according to `subsampling`
+/// * `subsampling`: Chroma subsampling of both images, see [YuvSubsampling] for more info
+/// * `siting`: Chroma sample position of both images, see [ChromaSiting] for more info
+/// * `bit_depth`: Bit-depth of the image, must be in range 1..=16
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Limitations
+///
+/// This is using integral approximations for images 10, 12 bit depth,
+/// same as [crate::resize_plane16] does.
+///
+pub fn resize_yuv16(
+    source: &YuvPlanarImage<u16>,
+    destination: &mut YuvPlanarImageMut<u16>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+) -> Result<(), PicScaleError> {
+    resize_yuv_planes(
+        source,
+        destination,
+        subsampling,
+        siting,
+        resampling_function,
+        |source_size, destination_size, layout, options| {
+            ResizePlan::<u16>::with_options(
+                source_size,
+                destination_size,
+                layout,
+                bit_depth,
+                options,
+            )
+        },
+        |plan, source, source_stride, destination, destination_stride, scratch| {
+            plan.resize_with_stride(
+                source,
+                source_stride,
+                destination,
+                destination_stride,
+                scratch,
+            )
+        },
+    )
+}
+
+/// Performs resizing on semi-planar YUV 8 bit-depth image
+///
+/// Handles NV12 and NV21 for 4:2:0, NV16 and NV61 for 4:2:2, NV24 and NV42 for 4:4:4,
+/// order of chroma in pairs is preserved. Chroma is resampled at the positions
+/// given by `siting`, see [resize_yuv8] for more info.
+///
+/// # Arguments
+///
+/// * `source`: Source image, chroma plane must be sized according to `subsampling`
+/// * `destination`: Destination image, chroma plane must be sized according to `subsampling`
+/// * `subsampling`: Chroma subsampling of both images, see [YuvSubsampling] for more info
+/// * `siting`: Chroma sample position of both images, see [ChromaSiting] for more info
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{
+///     resize_yuv_nv8, ChromaSiting, ImageSize, ResamplingFunction, YuvSemiPlanarImage,
+///     YuvSemiPlanarImageMut, YuvSubsampling,
+/// };
+///
+/// // NV12 1920x1080 to 1280x720
+/// let y = vec![16u8; 1920 * 1080];
+/// let uv = vec![128u8; 960 * 540 * 2];
+/// let source = YuvSemiPlanarImage {
+///     y_plane: &y,
+///     y_stride: 1920,
+///     uv_plane: &uv,
+///     uv_stride: 1920,
+///     size: ImageSize::new(1920, 1080),
+/// };
+/// let mut dst_y = vec![0u8; 1280 * 720];
+/// let mut dst_uv = vec![0u8; 640 * 360 * 2];
+/// let mut destination = YuvSemiPlanarImageMut {
+///     y_plane: &mut dst_y,
+///     y_stride: 1280,
+///     uv_plane: &mut dst_uv,
+///     uv_stride: 1280,
+///     size: ImageSize::new(1280, 720),
+/// };
+/// resize_yuv_nv8(
+///     &source,
+///     &mut destination,
+///     YuvSubsampling::Yuv420,
+///     ChromaSiting::Left,
+///     ResamplingFunction::Bilinear,
+/// )
+/// .unwrap();
+/// ```
+///
+/// # Limitations
+///
+/// This is using integral approximations, same as [crate::resize_plane8] does.
+///
+pub fn resize_yuv_nv8(
+    source: &YuvSemiPlanarImage<u8>,
+    destination: &mut YuvSemiPlanarImageMut<u8>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    resampling_function: ResamplingFunction,
+) -> Result<(), PicScaleError> {
+    resize_yuv_semi_planar(
+        source,
+        destination,
+        subsampling,
+        siting,
+        resampling_function,
+        |source_size, destination_size, layout, options| {
+            ResizePlan::<u8>::with_options(source_size, destination_size, layout, options)
+        },
+        |plan, source, source_stride, destination, destination_stride, scratch| {
+            plan.resize_with_stride(
+                source,
+                source_stride,
+                destination,
+                destination_stride,
+                scratch,
+            )
+        },
+    )
+}
+
+/// Performs resizing on semi-planar YUV 8-16 bit-depth image
+///
+/// Same as [resize_yuv_nv8], but for high bit-depth images such as P010, P016 or P210.
+///
+/// With [YuvBitPacking::Msb] samples are resized as 16 bit-depth
+/// and rounded back to `bit_depth` significant bits, so low bits stay zero.
+///
+/// # Arguments
+///
+/// * `source`: Source image, chroma plane must be sized according to `subsampling`
+/// * `destination`: Destination image, chroma plane must be sized according to `subsampling`
+/// * `subsampling`: Chroma subsampling of both images, see [YuvSubsampling] for more info
+/// * `siting`: Chroma sample position of both images, see [ChromaSiting] for more info
+/// * `bit_depth`: Significant bits of samples, must be in range 1..=16, `10` for P010
+/// * `packing`: Placement of samples inside `u16`, see [YuvBitPacking] for more info
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Limitations
+///
+/// This is using integral approximations for LSB-aligned images 10, 12 bit depth,
+/// same as [crate::resize_plane16] does.
+///
+pub fn resize_yuv_nv16(
+    source: &YuvSemiPlanarImage<u16>,
+    destination: &mut YuvSemiPlanarImageMut<u16>,
+    subsampling: YuvSubsampling,
+    siting: ChromaSiting,
+    bit_depth: u32,
+    packing: YuvBitPacking,
+    resampling_function: ResamplingFunction,
+) -> Result<(), PicScaleError> {
+    check_bit_depth(bit_depth)?;
+    let plan_bit_depth = match packing {
+        YuvBitPacking::Lsb => bit_depth,
+        YuvBitPacking::Msb => 16,
+    };
+    resize_yuv_semi_planar(
+        source,
+        destination,
+        subsampling,
+        siting,
+        resampling_function,
+        |source_size, destination_size, layout, options| {
+            ResizePlan::<u16>::with_options(
+                source_size,
+                destination_size,
+                layout,
+                plan_bit_depth,
+                options,
+            )
+        },
+        |plan, source, source_stride, destination, destination_stride, scratch| {
+            plan.resize_with_stride(
+                source,
+                source_stride,
+                destination,
+                destination_stride,
+                scratch,
+            )
+        },
+    )?;
+    if packing == YuvBitPacking::Msb {
+        let chroma_size = subsampling.chroma_size(destination.size);
+        align_msb(
+            destination.y_plane,
+            destination.y_stride,
+            destination.size,
+            1,
+            bit_depth,
+        );
+        align_msb(
+            destination.uv_plane,
+            destination.uv_stride,
+            chroma_size,
+            2,
+            bit_depth,
+        );
+    }
+    Ok(())
+}
+
+/// Converts chroma plane between formats, creating plan with `make_plan`
+fn resample_chroma<T: Copy + Default>(
+    source: &[T],
+    luma_size: ImageSize,
+    source_format: ChromaFormat,
+    destination_format: ChromaFormat,
+    resampling_function: ResamplingFunction,
+    make_plan: impl Fn(ImageSize, ImageSize, ResizeOptions) -> Result<ResizePlan<T>, PicScaleError>,
+    execute: impl Fn(&ResizePlan<T>, &[T], &mut [T]) -> Result<(), PicScaleError>,
+) -> Result<Vec<T>, PicScaleError> {
+    let destination_size = destination_format.subsampling.chroma_size(luma_size);
+    let plan = make_plan(
+        source_format.subsampling.chroma_size(luma_size),
+        destination_size,
+        chroma_resize_options(
+            luma_size,
+            source_format,
+            luma_size,
+            destination_format,
+            ResizeOptions::new(resampling_function),
+        ),
+    )?;
+    let mut destination = vec![T::default(); destination_size.width * destination_size.height];
+    execute(&plan, source, &mut destination)?;
+    Ok(destination)
+}
+
+/// Converts 8 bit-depth chroma plane into another subsampling or siting
+///
+/// Chroma is filtered with `resampling_function` at the positions of destination
+/// chroma samples, so 4:2:0 to 4:4:4 is a proper interpolation instead of duplication,
+/// and 4:4:4 to 4:2:0 is a proper decimation instead of dropping samples.
+///
+/// # Arguments
+///
+/// * `source`: Source chroma plane, tightly packed and sized according to source subsampling
+/// * `luma_size`: Luma size of the image
+/// * `source_format`: Subsampling and siting of the source, see [ChromaFormat] for more info
+/// * `destination_format`: Subsampling and siting of the result, see [ChromaFormat] for more info
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Chroma plane sized according to destination subsampling
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{
+///     resample_chroma8, ChromaFormat, ChromaSiting, ImageSize, ResamplingFunction,
+///     YuvSubsampling,
+/// };
+///
+/// // 4:2:0 plane of 1920x1080 frame upsampled to 4:2:2
+/// let u = vec![128u8; 960 * 540];
+/// let upsampled = resample_chroma8(
+///     &u,
+///     ImageSize::new(1920, 1080),
+///     ChromaFormat::new(YuvSubsampling::Yuv420, ChromaSiting::Left),
+///     ChromaFormat::new(YuvSubsampling::Yuv422, ChromaSiting::Left),
+///     ResamplingFunction::CatmullRom,
+/// )
+/// .unwrap();
+/// assert_eq!(upsampled.len(), 960 * 1080);
+/// ```
+///
+/// # Limitations
+///
+/// This is using integral approximations, same as [crate::resize_plane8] does.
+///
+pub fn resample_chroma8(
+    source: &[u8],
+    luma_size: ImageSize,
+    source_format: ChromaFormat,
+    destination_format: ChromaFormat,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u8>, PicScaleError> {
+    resample_chroma(
+        source,
+        luma_size,
+        source_format,
+        destination_format,
+        resampling_function,
+        |source_size, destination_size, options| {
+            ResizePlan::<u8>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                options,
+            )
+        },
+        |plan, source, destination| plan.resize(source, destination),
+    )
+}
+
+/// Converts 8-16 bit-depth chroma plane into another subsampling or siting
+///
+/// Same as [resample_chroma8], but for high bit-depth planes where samples are stored
+/// in the low bits of `u16`.
+///
+/// # Arguments
+///
+/// * `source`: Source chroma plane, tightly packed and sized according to source subsampling
+/// * `luma_size`: Luma size of the image
+/// * `source_format`: Subsampling and siting of the source, see [ChromaFormat] for more info
+/// * `destination_format`: Subsampling and siting of the result, see [ChromaFormat] for more info
+/// * `bit_depth`: Bit-depth of the plane, must be in range 1..=16
+/// * `resampling_function`: Resampling filter, see [ResamplingFunction] for more info
+///
+/// # Returns
+///
+/// Chroma plane sized according to destination subsampling
+///
+/// # Limitations
+///
+/// This is using integral approximations for images 10, 12 bit depth,
+/// same as [crate::resize_plane16] does.
+///
+pub fn resample_chroma16(
+    source: &[u16],
+    luma_size: ImageSize,
+    source_format: ChromaFormat,
+    destination_format: ChromaFormat,
+    bit_depth: u32,
+    resampling_function: ResamplingFunction,
+) -> Result<Vec<u16>, PicScaleError> {
+    resample_chroma(
+        source,
+        luma_size,
+        source_format,
+        destination_format,
+        resampling_function,
+        |source_size, destination_size, options| {
+            ResizePlan::<u16>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                bit_depth,
+                options,
+            )
+        },
+        |plan, source, destination| plan.resize(source, destination),
+    )
+}
@@ -0,0 +1,191 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::anti_ringing::{anti_ring_column, anti_ring_row, AntiRingingSample};
+use crate::edge_mode::{column_source, fill_row_padding};
+use crate::filter_weights::{FilterBounds, FilterWeights};
+use crate::image_size::ImageSize;
+use crate::threading::Executor;
+use crate::validation::scratch_slice;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+#[cfg(feature = "rayon")]
+use rayon::prelude::{ParallelSlice, ParallelSliceMut};
+
+/// Conversion applied to each row while it is loaded into intermediate and stored back
+///
+/// # Generics
+///
+/// * `T` - image data type
+/// * `I` - intermediate data type
+pub(crate) trait RowTransform<T, I>: Sync {
+    /// Converts source row into intermediate, both are exactly `width * channels`
+    fn load(&self, src: &[T], dst: &mut [I]);
+
+    /// Converts intermediate row back, both are exactly `width * channels`
+    fn store(&self, src: &[I], dst: &mut [T]);
+}
+
+/// Separable convolution where rows are converted on the fly
+///
+/// Horizontal pass goes first, so each source row is transformed right before it is convolved,
+/// and each destination row is transformed back right after vertical pass computed it.
+/// Intermediate image is kept in `scratch`. Layouts must be validated by the caller.
+///
+/// # Arguments
+///
+/// * `vertical_filters`: Vertical weights, `None` if height is not changing
+/// * `horizontal_filters`: Horizontal weights, `None` if width is not changing
+/// * `row_handler`: Convolves one intermediate row with horizontal weights
+/// * `column_handler`: Convolves one intermediate row with vertical weights
+///
+/// Anti-ringing clamp of the weights is applied after each handler.
+///
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn convolve_transformed<T, I, W, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    source_size: ImageSize,
+    dst: &mut [T],
+    dst_stride: usize,
+    destination_size: ImageSize,
+    vertical_filters: Option<&FilterWeights<W>>,
+    horizontal_filters: Option<&FilterWeights<W>>,
+    transform: &impl RowTransform<T, I>,
+    row_handler: impl Fn(&[I], &mut [I], &FilterWeights<W>) + Sync,
+    column_handler: impl Fn(&FilterBounds, &[I], &mut [I], usize, &[W]) + Sync,
+    scratch: &mut Vec<I>,
+    executor: &Executor,
+) where
+    T: Copy + Send + Sync,
+    I: Copy + Default + Send + Sync + AntiRingingSample,
+    W: Sync,
+{
+    // Intermediate image is always tightly packed
+    let src_row_length = source_size.width * CHANNELS;
+    let transient_stride = destination_size.width * CHANNELS;
+    let transient = scratch_slice(scratch, transient_stride * source_size.height);
+
+    let padding = horizontal_filters.map_or(0, |filters| filters.padding);
+    let padded_row_length = (source_size.width + 2 * padding) * CHANNELS;
+
+    let horizontal_pass = |src: &[T], dst: &mut [I], row: &mut Vec<I>| {
+        let src = &src[..src_row_length];
+        if let Some(horizontal_filters) = horizontal_filters {
+            let offset = padding * CHANNELS;
+            transform.load(src, &mut row[offset..offset + src_row_length]);
+            fill_row_padding::<I, CHANNELS>(
+                row,
+                source_size.width,
+                padding,
+                horizontal_filters.edge_mode,
+            );
+            row_handler(row, dst, horizontal_filters);
+            if let Some(anti_ringing) = &horizontal_filters.anti_ringing {
+                anti_ring_row::<I, CHANNELS>(row, dst, &horizontal_filters.bounds, anti_ringing);
+            }
+        } else {
+            transform.load(src, dst);
+        }
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            src.par_chunks(src_stride)
+                .zip(transient.par_chunks_exact_mut(transient_stride))
+                .with_min_len(executor.min_len(destination_size.width))
+                .for_each_init(
+                    || vec![I::default(); padded_row_length],
+                    |row, (src, dst)| horizontal_pass(src, dst, row),
+                );
+        });
+    } else {
+        let mut row = vec![I::default(); padded_row_length];
+        for (src, dst) in src
+            .chunks(src_stride)
+            .zip(transient.chunks_exact_mut(transient_stride))
+        {
+            horizontal_pass(src, dst, &mut row);
+        }
+    }
+
+    let transient: &[I] = transient;
+
+    let vertical_pass = |y: usize, dst: &mut [T], row: &mut Vec<I>, rows: &mut Vec<I>| {
+        let dst = &mut dst[..transient_stride];
+        if let Some(vertical_filters) = vertical_filters {
+            let aligned_size = vertical_filters.aligned_size;
+            let weights = &vertical_filters.weights[y * aligned_size..(y + 1) * aligned_size];
+            let (bounds, src, src_stride) = column_source(
+                &vertical_filters.bounds[y],
+                transient,
+                transient_stride,
+                transient_stride,
+                source_size.height,
+                vertical_filters.padding,
+                vertical_filters.edge_mode,
+                rows,
+            );
+            column_handler(&bounds, src, row, src_stride, weights);
+            if let Some(anti_ringing) = &vertical_filters.anti_ringing {
+                anti_ring_column(
+                    &bounds,
+                    &anti_ringing.taps[y],
+                    src,
+                    src_stride,
+                    row,
+                    anti_ringing.strength,
+                );
+            }
+            transform.store(row, dst);
+        } else {
+            let src = &transient[y * transient_stride..(y + 1) * transient_stride];
+            transform.store(src, dst);
+        }
+    };
+
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            dst.par_chunks_mut(dst_stride)
+                .with_min_len(executor.min_len(destination_size.width))
+                .enumerate()
+                .for_each_init(
+                    || (vec![I::default(); transient_stride], Vec::new()),
+                    |(row, rows), (y, dst)| vertical_pass(y, dst, row, rows),
+                );
+        });
+    } else {
+        let mut row = vec![I::default(); transient_stride];
+        let mut rows = Vec::new();
+        for (y, dst) in dst.chunks_mut(dst_stride).enumerate() {
+            vertical_pass(y, dst, &mut row, &mut rows);
+        }
+    }
+}
@@ -28,21 +28,23 @@
  */
 #![allow(clippy::excessive_precision)]
 
+use crate::custom_filter::CustomFilter;
+use crate::error::PicScaleError;
 use crate::math::bartlett::{bartlett, bartlett_hann};
 use crate::math::bc_spline::{
-    b_spline, catmull_rom, hermite_spline, mitchell_netravalli, robidoux, robidoux_sharp,
+    b_spline, bc_spline, catmull_rom, hermite_spline, mitchell_netravalli, robidoux, robidoux_sharp,
 };
 use crate::math::bilinear::bilinear;
-use crate::math::blackman::blackman;
+use crate::math::blackman::{blackman, blackman_sinc};
 use crate::math::bohman::bohman;
 use crate::math::cubic::{bicubic_spline, cubic_spline};
-use crate::math::gaussian::gaussian;
+use crate::math::gaussian::{gaussian, gaussian_sigma};
 use crate::math::hann::{hamming, hann, hanning};
-use crate::math::kaiser::kaiser;
+use crate::math::kaiser::{kaiser, kaiser_beta};
 use crate::math::lagrange::{lagrange2, lagrange3};
 use crate::math::lanczos::{
     lanczos2, lanczos2_jinc, lanczos3, lanczos3_jinc, lanczos4, lanczos4_jinc, lanczos6,
-    lanczos6_jinc,
+    lanczos6_jinc, lanczos_sinc,
 };
 use crate::math::quadric::quadric;
 use crate::math::sinc::sinc;
@@ -51,8 +53,16 @@ use crate::math::spline_n::{spline16, spline36, spline64};
 use crate::math::welch::welch;
 use crate::math::{ConstPI, ConstSqrt2, Jinc};
 use num_traits::{AsPrimitive, Float, Signed};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use std::ops::{AddAssign, MulAssign, Neg};
 
+/// Kaiser window with bigger `beta` is already narrower than a single tap
+const MAX_KAISER_BETA: f32 = 50.;
+
+/// Widest support of a filter in pixels before it's stretched by downscale or blur
+pub(crate) const MAX_KERNEL_RADIUS: f32 = 64.;
+
 #[inline(always)]
 pub(crate) fn box_weight<V: Copy + 'static>(_: V) -> V
 where
@@ -61,10 +71,58 @@ where
     1f32.as_()
 }
 
-#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq)]
+/// Parameter of [ResamplingFunction], totally ordered so functions can be compared and sorted
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::ResamplingFunction;
+///
+/// let smoother = ResamplingFunction::KaiserBeta { beta: 8.0.into() };
+/// let sharper = ResamplingFunction::KaiserBeta { beta: 4.0.into() };
+/// assert!(sharper < smoother);
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FilterParameter(pub f32);
+
+impl From<f32> for FilterParameter {
+    fn from(value: f32) -> Self {
+        FilterParameter(value)
+    }
+}
+
+impl PartialEq for FilterParameter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FilterParameter {}
+
+impl PartialOrd for FilterParameter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FilterParameter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Consistent with [f32::total_cmp], parameters are equal only when bits are
+impl Hash for FilterParameter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
 /// Describes resampling function that will be used
 pub enum ResamplingFunction {
     Bilinear,
+    /// Upscale by integral factors along both axes repeats pixels without index tables
     Nearest,
     Cubic,
     #[default]
@@ -104,7 +162,46 @@ pub enum ResamplingFunction {
     Lanczos6,
     Lanczos6Jinc,
     /// This method replicates `INTER_AREA` behaviour from OpenCV
+    ///
+    /// Downscale by integral factors along both axes averages blocks directly
+    /// with rounding to the nearest, instead of going through kernel weights,
+    /// unless blur, taper or anti-ringing is set in [crate::ResizeOptions].
     Area,
+    /// Kaiser window with custom `beta`, [ResamplingFunction::Kaiser] uses `6.33`.
+    /// Bigger `beta` is smoother, smaller is sharper.
+    ///
+    /// `beta` must be in range `0..=50`.
+    KaiserBeta {
+        beta: FilterParameter,
+    },
+    /// Gaussian with standard deviation `sigma` in pixels, support is `3 * sigma`
+    ///
+    /// `sigma` must be in range `0..=64/3`, zero excluded, so support is at most 64 pixels.
+    GaussianSigma {
+        sigma: FilterParameter,
+    },
+    /// Mitchell-Netravali family of cubic splines.
+    ///
+    /// `b = 1/3, c = 1/3` is [ResamplingFunction::MitchellNetravalli],
+    /// `b = 0, c = 0.5` is [ResamplingFunction::CatmullRom], `b = 1, c = 0` is [ResamplingFunction::BSpline].
+    BcSpline {
+        b: FilterParameter,
+        c: FilterParameter,
+    },
+    /// Lanczos with any amount of lobes, `lobes = 3` is [ResamplingFunction::Lanczos3]
+    ///
+    /// `lobes` must be in range `1..=64`.
+    Lanczos {
+        lobes: u32,
+    },
+    /// Sinc windowed by Blackman window with any amount of lobes
+    ///
+    /// `lobes` must be in range `1..=64`.
+    BlackmanSinc {
+        lobes: u32,
+    },
+    /// User defined filter, see [CustomFilter] for more info
+    Custom(CustomFilter),
 }
 
 impl From<u32> for ResamplingFunction {
@@ -154,18 +251,22 @@ impl From<u32> for ResamplingFunction {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
 pub(crate) struct ResamplingWindow<T> {
-    pub(crate) window: fn(T) -> T,
+    pub(crate) window: Box<dyn Fn(T) -> T>,
     pub(crate) window_size: f32,
     pub(crate) blur: f32,
     pub(crate) taper: f32,
 }
 
 impl<T> ResamplingWindow<T> {
-    fn new(window: fn(T) -> T, window_size: f32, blur: f32, taper: f32) -> ResamplingWindow<T> {
+    pub(crate) fn new(
+        window: impl Fn(T) -> T + 'static,
+        window_size: f32,
+        blur: f32,
+        taper: f32,
+    ) -> ResamplingWindow<T> {
         ResamplingWindow {
-            window,
+            window: Box::new(window),
             window_size,
             blur,
             taper,
@@ -173,9 +274,8 @@ impl<T> ResamplingWindow<T> {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
 pub(crate) struct ResamplingFilter<T> {
-    pub kernel: fn(T) -> T,
+    pub kernel: Box<dyn Fn(T) -> T>,
     pub window: Option<ResamplingWindow<T>>,
     pub min_kernel_size: f32,
     pub is_resizable_kernel: bool,
@@ -183,9 +283,9 @@ pub(crate) struct ResamplingFilter<T> {
 }
 
 impl<T> ResamplingFilter<T> {
-    fn new(kernel: fn(T) -> T, min_kernel_size: f32) -> ResamplingFilter<T> {
+    fn new(kernel: impl Fn(T) -> T + 'static, min_kernel_size: f32) -> ResamplingFilter<T> {
         ResamplingFilter {
-            kernel,
+            kernel: Box::new(kernel),
             window: None,
             min_kernel_size,
             is_resizable_kernel: true,
@@ -193,9 +293,9 @@ impl<T> ResamplingFilter<T> {
         }
     }
 
-    fn new_area(kernel: fn(T) -> T, min_kernel_size: f32) -> ResamplingFilter<T> {
+    fn new_area(kernel: impl Fn(T) -> T + 'static, min_kernel_size: f32) -> ResamplingFilter<T> {
         ResamplingFilter {
-            kernel,
+            kernel: Box::new(kernel),
             window: None,
             min_kernel_size,
             is_resizable_kernel: true,
@@ -203,13 +303,13 @@ impl<T> ResamplingFilter<T> {
         }
     }
 
-    fn new_with_window(
-        kernel: fn(T) -> T,
+    pub(crate) fn new_with_window(
+        kernel: impl Fn(T) -> T + 'static,
         window: ResamplingWindow<T>,
         min_kernel_size: f32,
     ) -> ResamplingFilter<T> {
         ResamplingFilter::<T> {
-            kernel,
+            kernel: Box::new(kernel),
             window: Some(window),
             min_kernel_size,
             is_resizable_kernel: true,
@@ -217,9 +317,12 @@ impl<T> ResamplingFilter<T> {
         }
     }
 
-    fn new_with_fixed_kernel(kernel: fn(T) -> T, min_kernel_size: f32) -> ResamplingFilter<T> {
+    fn new_with_fixed_kernel(
+        kernel: impl Fn(T) -> T + 'static,
+        min_kernel_size: f32,
+    ) -> ResamplingFilter<T> {
         ResamplingFilter::<T> {
-            kernel,
+            kernel: Box::new(kernel),
             window: None,
             min_kernel_size,
             is_resizable_kernel: false,
@@ -300,6 +403,48 @@ impl ResamplingFunction {
             ResamplingFunction::Lagrange3 => ResamplingFilter::new(lagrange3, 3f32),
             ResamplingFunction::Lanczos6Jinc => ResamplingFilter::new(lanczos6_jinc, 6f32),
             ResamplingFunction::Lanczos6 => ResamplingFilter::new(lanczos6, 6f32),
+            ResamplingFunction::KaiserBeta { beta } => {
+                let beta: T = beta.0.as_();
+                ResamplingFilter::new(move |x| kaiser_beta(x, beta), 2f32)
+            }
+            ResamplingFunction::GaussianSigma { sigma } => {
+                let radius = (3f32 * sigma.0).max(1f32);
+                let sigma: T = sigma.0.as_();
+                ResamplingFilter::new(move |x| gaussian_sigma(x, sigma), radius)
+            }
+            ResamplingFunction::BcSpline { b, c } => {
+                let (b, c): (T, T) = (b.0.as_(), c.0.as_());
+                ResamplingFilter::new(move |x| bc_spline(x, b, c), 2f32)
+            }
+            ResamplingFunction::Lanczos { lobes } => {
+                let a: T = (*lobes as f32).as_();
+                ResamplingFilter::new(move |x| lanczos_sinc(x, a), *lobes as f32)
+            }
+            ResamplingFunction::BlackmanSinc { lobes } => {
+                let a: T = (*lobes as f32).as_();
+                ResamplingFilter::new(move |x| blackman_sinc(x, a), *lobes as f32)
+            }
+            ResamplingFunction::Custom(filter) => filter.resampling_filter(),
+        }
+    }
+
+    /// Checks that parameters of the function are usable
+    pub(crate) fn validate(&self) -> Result<(), PicScaleError> {
+        let valid = match *self {
+            ResamplingFunction::KaiserBeta { beta } => (0. ..=MAX_KAISER_BETA).contains(&beta.0),
+            ResamplingFunction::GaussianSigma { sigma } => {
+                sigma.0 > 0. && sigma.0 * 3. <= MAX_KERNEL_RADIUS
+            }
+            ResamplingFunction::BcSpline { b, c } => b.0.is_finite() && c.0.is_finite(),
+            ResamplingFunction::Lanczos { lobes } | ResamplingFunction::BlackmanSinc { lobes } => {
+                lobes > 0 && lobes as f32 <= MAX_KERNEL_RADIUS
+            }
+            ResamplingFunction::Custom(filter) => return filter.validate(),
+            _ => true,
+        };
+        if !valid {
+            return Err(PicScaleError::InvalidResamplingFunction);
         }
+        Ok(())
     }
 }
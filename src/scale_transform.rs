@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::error::PicScaleError;
+use crate::source_rect::AxisMapping;
+
+/// Arbitrary placement of the destination over the source
+///
+/// Center of destination pixel `x` is sampled at `offset_x + (x + 0.5) / scale_x` of the source,
+/// and the same for `y`, so `scale` is a magnification factor and `offset` is in source pixels.
+/// Scale must be at least [ScaleTransform::MIN_SCALE], so destination pixel never covers
+/// more than 4096 source pixels along an axis.
+/// Parts of destination which are mapped outside the source are sampled
+/// according to [crate::EdgeMode].
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{
+///     ChannelLayout, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan, ScaleTransform,
+/// };
+///
+/// // 2x zoom shifted by 1.5 pixels, destination is covering 32x32 area of the source
+/// let options = ResizeOptions::new(ResamplingFunction::Lanczos3)
+///     .with_scale_transform(ScaleTransform::new(2., 2., 1.5, 1.5));
+/// let plan = ResizePlan::<f32>::with_options(
+///     ImageSize::new(64, 64),
+///     ImageSize::new(64, 64),
+///     ChannelLayout::Plane,
+///     options,
+/// )
+/// .unwrap();
+/// let source = vec![0f32; 64 * 64];
+/// let mut destination = vec![0f32; 64 * 64];
+/// plan.resize(&source, &mut destination).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScaleTransform {
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+}
+
+impl Default for ScaleTransform {
+    fn default() -> Self {
+        ScaleTransform::new(1., 1., 0., 0.)
+    }
+}
+
+impl ScaleTransform {
+    /// Smallest accepted scale, as kernel grows with minification and so does memory for weights
+    pub const MIN_SCALE: f32 = 1. / 4096.;
+
+    pub fn new(scale_x: f32, scale_y: f32, offset_x: f32, offset_y: f32) -> ScaleTransform {
+        ScaleTransform {
+            scale_x,
+            scale_y,
+            offset_x,
+            offset_y,
+        }
+    }
+
+    /// Checks that scales are not below [ScaleTransform::MIN_SCALE] and everything is finite
+    pub(crate) fn validate(&self) -> Result<(), PicScaleError> {
+        let valid = |scale: f32, offset: f32| {
+            scale.is_finite() && scale >= ScaleTransform::MIN_SCALE && offset.is_finite()
+        };
+        if !valid(self.scale_x, self.offset_x) || !valid(self.scale_y, self.offset_y) {
+            return Err(PicScaleError::InvalidScaleTransform);
+        }
+        Ok(())
+    }
+
+    /// Mappings of destination for horizontal and vertical axis
+    pub(crate) fn axis_mappings(&self) -> (AxisMapping, AxisMapping) {
+        (
+            AxisMapping {
+                offset: self.offset_x as f64,
+                scale: 1. / self.scale_x as f64,
+            },
+            AxisMapping {
+                offset: self.offset_y as f64,
+                scale: 1. / self.scale_y as f64,
+            },
+        )
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::error::PicScaleError;
+use crate::image_size::ImageSize;
+
+/// Rectangle of the source image which is resized into the whole destination
+///
+/// Coordinates are in pixels and may be fractional, pixel `x` covers range `x..x + 1`.
+/// Pixels outside the rectangle are still sampled when kernel reaches them,
+/// so result is the same as if the region were cut from a larger resize.
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{
+///     ChannelLayout, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan, SourceRect,
+/// };
+///
+/// // Scales region of 640x480 image into 64x48 thumbnail
+/// let options = ResizeOptions::new(ResamplingFunction::Lanczos3)
+///     .with_source_rect(SourceRect::new(20.25, 10.5, 100.5, 80.25));
+/// let plan = ResizePlan::<u8>::with_options(
+///     ImageSize::new(640, 480),
+///     ImageSize::new(64, 48),
+///     ChannelLayout::Rgb,
+///     options,
+/// )
+/// .unwrap();
+/// let source = vec![0u8; 640 * 480 * 3];
+/// let mut destination = vec![0u8; 64 * 48 * 3];
+/// plan.resize(&source, &mut destination).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct SourceRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl SourceRect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> SourceRect {
+        SourceRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Checks that rectangle is not empty and lays inside the image
+    pub(crate) fn validate(&self, source_size: ImageSize) -> Result<(), PicScaleError> {
+        let fits = |start: f32, size: f32, image_size: usize| {
+            start.is_finite()
+                && size.is_finite()
+                && start >= 0.
+                && size > 0.
+                && start as f64 + size as f64 <= image_size as f64
+        };
+        if !fits(self.x, self.width, source_size.width)
+            || !fits(self.y, self.height, source_size.height)
+        {
+            return Err(PicScaleError::InvalidSourceRect);
+        }
+        Ok(())
+    }
+
+    /// Mappings of destination onto this rectangle for horizontal and vertical axis
+    pub(crate) fn axis_mappings(&self, destination_size: ImageSize) -> (AxisMapping, AxisMapping) {
+        (
+            AxisMapping::new(self.x, self.width, destination_size.width),
+            AxisMapping::new(self.y, self.height, destination_size.height),
+        )
+    }
+}
+
+/// Destination pixel `i` is sampled at `offset + (i + 0.5) * scale` in source coordinates
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct AxisMapping {
+    pub(crate) offset: f64,
+    pub(crate) scale: f64,
+}
+
+impl AxisMapping {
+    fn new(start: f32, size: f32, out_size: usize) -> AxisMapping {
+        AxisMapping {
+            offset: start as f64,
+            scale: size as f64 / out_size as f64,
+        }
+    }
+}
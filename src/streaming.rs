@@ -0,0 +1,554 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::alpha_transform::AlphaTransform;
+use crate::anti_ringing::{anti_ring_column, AntiRingingSample};
+use crate::edge_mode::EdgeMode;
+use crate::error::{MismatchedSize, PicScaleError};
+use crate::filter_weights::{FilterBounds, FilterWeights};
+use crate::fixed_point_dispatch::convolve_row_fixed_point;
+use crate::floating_point_dispatch::convolve_row_floating_point;
+use crate::handler_provider::{ColumnHandlerFixedPoint, ColumnHandlerFloatingPoint};
+use crate::image_size::ImageSize;
+use crate::resize_nearest::resize_nearest;
+use crate::resize_options::PremultiplyMode;
+use crate::resize_plan::{ChannelLayout, PlanWeights, ResizePlan};
+use crate::resize_weights::ResizeWeights;
+use crate::row_transform::RowTransform;
+use crate::threading::Executor;
+use crate::validation::scratch_slice;
+
+/// Source rows required by each destination row
+#[derive(Debug, Clone)]
+struct RowsLayout {
+    /// Bounds in coordinates of the source padded by `padding` rows
+    bounds: Vec<FilterBounds>,
+    padding: usize,
+    edge_mode: EdgeMode,
+}
+
+impl RowsLayout {
+    fn new<W>(weights: &ResizeWeights<W>, destination_height: usize) -> RowsLayout {
+        let single_rows = |rows: Vec<FilterBounds>| RowsLayout {
+            bounds: rows,
+            padding: 0,
+            edge_mode: EdgeMode::Shrink,
+        };
+        match weights {
+            ResizeWeights::IntegerRatio { general, .. } => {
+                RowsLayout::new(general, destination_height)
+            }
+            ResizeWeights::Nearest { rows, .. } => single_rows(
+                rows.iter()
+                    .map(|row| row.map_or(FilterBounds::new(0, 0), |y| FilterBounds::new(y, 1)))
+                    .collect(),
+            ),
+            ResizeWeights::Convolution {
+                vertical: Some(vertical),
+                ..
+            } => RowsLayout {
+                bounds: vertical.bounds.clone(),
+                padding: vertical.padding,
+                edge_mode: vertical.edge_mode,
+            },
+            ResizeWeights::Copy | ResizeWeights::Convolution { vertical: None, .. } => single_rows(
+                (0..destination_height)
+                    .map(|y| FilterBounds::new(y, 1))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn source_row(&self, padded_row: usize, height: usize) -> usize {
+        self.edge_mode
+            .map(padded_row as i64 - self.padding as i64, height)
+    }
+
+    /// First and last source row read for destination row, `None` if nothing is read
+    fn source_range(&self, y: usize, height: usize) -> Option<(usize, usize)> {
+        let bounds = self.bounds[y];
+        if bounds.size == 0 {
+            return None;
+        }
+        (bounds.start..bounds.start + bounds.size)
+            .map(|row| self.source_row(row, height))
+            .fold(None, |range, row| match range {
+                None => Some((row, row)),
+                Some((lo, hi)) => Some((row.min(lo), row.max(hi))),
+            })
+    }
+}
+
+/// Resizes image pushed row by row, emitting destination rows as soon as they can be computed
+///
+/// Each pushed row goes through horizontal pass right away and is kept in a ring buffer,
+/// destination row is computed once all source rows under its vertical kernel are pushed.
+/// Only a window of rows is kept in memory, so images larger than memory might be resized
+/// together with a streaming decoder and encoder. [crate::EdgeMode::Wrap] is the exception,
+/// it reads rows from the other side of the image, so the whole image is buffered.
+///
+/// Output is the same as [ResizePlan] produces with [crate::PassOrder::HorizontalFirst],
+/// except integral ratio [crate::ResamplingFunction::Area] downscale, where the plan averages
+/// blocks directly and streaming goes through kernel weights, so they may differ by rounding.
+/// With [PremultiplyMode::Auto] alpha is associated even when it is constant across the image,
+/// because whole image is not known in advance.
+///
+/// Elliptical weighted averaging is not available for streaming.
+///
+/// # Example
+///
+/// ```
+/// use pic_scale_safe::{ChannelLayout, ImageSize, ResamplingFunction, ResizePlan, StreamingResizer};
+///
+/// let plan = ResizePlan::<u8>::new(
+///     ImageSize::new(64, 48),
+///     ImageSize::new(32, 24),
+///     ChannelLayout::Rgb,
+///     ResamplingFunction::Lanczos3,
+/// )
+/// .unwrap();
+/// let mut resizer = StreamingResizer::<u8>::new(plan).unwrap();
+/// let mut destination = vec![0u8; 32 * 24 * 3];
+/// let source_row = vec![0u8; 64 * 3];
+/// for _ in 0..48 {
+///     resizer
+///         .push_row(&source_row, |y, row| {
+///             destination[y * 32 * 3..(y + 1) * 32 * 3].copy_from_slice(row)
+///         })
+///         .unwrap();
+/// }
+/// assert!(resizer.is_finished());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingResizer<T> {
+    plan: ResizePlan<T>,
+    state: StreamState<T>,
+}
+
+/// Ring buffer of horizontally resized rows and progress of the stream
+#[derive(Debug, Clone)]
+struct StreamState<T> {
+    source_size: ImageSize,
+    channels: usize,
+    rows_layout: RowsLayout,
+    /// Last source row read by destination rows up to the given one
+    ready_after: Vec<Option<usize>>,
+    /// Count of rows in the ring, each row is stored twice to keep windows contiguous
+    capacity: usize,
+    ring: Vec<T>,
+    row: Vec<T>,
+    rows: Vec<T>,
+    destination_row: Vec<T>,
+    stored_row: Vec<T>,
+    rows_pushed: usize,
+    rows_emitted: usize,
+}
+
+impl<T: Copy + Default> StreamState<T> {
+    fn new<W>(plan: &ResizePlan<T>, weights: &ResizeWeights<W>) -> StreamState<T> {
+        let source_size = plan.source_size();
+        let destination_size = plan.destination_size();
+        let rows_layout = RowsLayout::new(weights, destination_size.height);
+        let ranges = (0..destination_size.height)
+            .map(|y| rows_layout.source_range(y, source_size.height))
+            .collect::<Vec<_>>();
+
+        let mut ready_after = Vec::with_capacity(ranges.len());
+        let mut last = None;
+        for range in ranges.iter() {
+            last = last.max(range.map(|range| range.1));
+            ready_after.push(last);
+        }
+        // Rows which are still required by the destination row and rows after it
+        let mut required_from = vec![usize::MAX; ranges.len() + 1];
+        for (y, range) in ranges.iter().enumerate().rev() {
+            required_from[y] = required_from[y + 1].min(range.map_or(usize::MAX, |range| range.0));
+        }
+        let mut capacity = 1;
+        let mut pending = 0;
+        for row in 0..source_size.height {
+            while pending < ready_after.len()
+                && !matches!(ready_after[pending], Some(last) if last >= row)
+            {
+                pending += 1;
+            }
+            if pending == ready_after.len() {
+                break;
+            }
+            if required_from[pending] <= row {
+                capacity = capacity.max(row - required_from[pending] + 1);
+            }
+        }
+
+        let channels = plan.layout().channels();
+        let row_length = destination_size.width * channels;
+        StreamState {
+            source_size,
+            channels,
+            rows_layout,
+            ready_after,
+            capacity,
+            ring: vec![T::default(); capacity * 2 * row_length],
+            row: Vec::new(),
+            rows: Vec::new(),
+            destination_row: vec![T::default(); row_length],
+            stored_row: vec![T::default(); row_length],
+            rows_pushed: 0,
+            rows_emitted: 0,
+        }
+    }
+
+    /// Pushes one source row and emits destination rows which became available
+    ///
+    /// # Arguments
+    ///
+    /// * `horizontal`: Horizontal pass of one row
+    /// * `vertical`: Vertical pass of destination row `y` with bounds, source and stride
+    /// * `transform`: Conversion applied when row is loaded and stored
+    ///
+    fn push_row(
+        &mut self,
+        source_row: &[T],
+        mut sink: impl FnMut(usize, &[T]),
+        horizontal: impl Fn(&[T], &mut [T]) -> Result<(), PicScaleError>,
+        vertical: impl Fn(usize, &FilterBounds, &[T], usize, &mut [T]),
+        transform: Option<&dyn RowTransform<T, T>>,
+    ) -> Result<(), PicScaleError> {
+        let source_size = self.source_size;
+        let source_row_length = source_size.width * self.channels;
+        if source_row.len() != source_row_length {
+            return Err(PicScaleError::SourceSizeMismatch(MismatchedSize {
+                expected: source_row_length,
+                received: source_row.len(),
+            }));
+        }
+        if self.rows_pushed == source_size.height {
+            return Err(PicScaleError::TooManyRows(source_size.height));
+        }
+
+        let row_length = self.destination_row.len();
+        let slot = self.rows_pushed % self.capacity;
+        {
+            let (first, second) = self.ring.split_at_mut(self.capacity * row_length);
+            let dst = &mut first[slot * row_length..(slot + 1) * row_length];
+            match transform {
+                Some(transform) => {
+                    let row = scratch_slice(&mut self.row, source_row_length);
+                    transform.load(source_row, row);
+                    horizontal(row, dst)?;
+                }
+                None => horizontal(source_row, dst)?,
+            }
+            second[slot * row_length..(slot + 1) * row_length].copy_from_slice(dst);
+        }
+        self.rows_pushed += 1;
+
+        while self.rows_emitted < self.ready_after.len()
+            && !matches!(self.ready_after[self.rows_emitted], Some(last) if last >= self.rows_pushed)
+        {
+            let y = self.rows_emitted;
+            let bounds = self.rows_layout.bounds[y];
+            let padding = self.rows_layout.padding;
+            let (bounds, src): (FilterBounds, &[T]) = if bounds.start >= padding
+                && bounds.start + bounds.size <= source_size.height + padding
+            {
+                let start = (bounds.start - padding) % self.capacity;
+                (FilterBounds::new(start, bounds.size), &self.ring)
+            } else {
+                let rows = scratch_slice(&mut self.rows, bounds.size * row_length);
+                for (j, dst) in rows.chunks_exact_mut(row_length).enumerate() {
+                    let row = self
+                        .rows_layout
+                        .source_row(bounds.start + j, source_size.height);
+                    let slot = row % self.capacity;
+                    dst.copy_from_slice(&self.ring[slot * row_length..(slot + 1) * row_length]);
+                }
+                (FilterBounds::new(0, bounds.size), rows)
+            };
+            vertical(y, &bounds, src, row_length, &mut self.destination_row);
+            match transform {
+                Some(transform) => {
+                    transform.store(&self.destination_row, &mut self.stored_row);
+                    sink(y, &self.stored_row);
+                }
+                None => sink(y, &self.destination_row),
+            }
+            self.rows_emitted += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<T> StreamingResizer<T> {
+    /// Plan this resizer executes
+    pub fn plan(&self) -> &ResizePlan<T> {
+        &self.plan
+    }
+
+    /// Count of source rows pushed so far
+    pub fn rows_pushed(&self) -> usize {
+        self.state.rows_pushed
+    }
+
+    /// Count of destination rows emitted so far
+    pub fn rows_emitted(&self) -> usize {
+        self.state.rows_emitted
+    }
+
+    /// Checks whether all destination rows are emitted
+    pub fn is_finished(&self) -> bool {
+        self.state.rows_emitted == self.plan.destination_size().height
+    }
+}
+
+/// Alpha can't be checked for being constant before the whole image is pushed,
+/// so it is associated whenever requested and pixels are not just copied
+fn needs_premultiplication<T>(plan: &ResizePlan<T>) -> bool {
+    let is_convolution = match plan.weights() {
+        PlanWeights::FixedPoint(weights) => {
+            matches!(weights.general(), ResizeWeights::Convolution { .. })
+        }
+        PlanWeights::FloatingPoint(weights) => {
+            matches!(weights.general(), ResizeWeights::Convolution { .. })
+        }
+        PlanWeights::Ewa(_) => false,
+    };
+    is_convolution
+        && plan.options().premultiply == PremultiplyMode::Auto
+        && matches!(
+            plan.layout(),
+            ChannelLayout::PlaneWithAlpha | ChannelLayout::Rgba
+        )
+}
+
+/// Horizontal pass of one row for any kind of weights
+fn horizontal_row<T, W, const CHANNELS: usize>(
+    weights: &ResizeWeights<W>,
+    src: &[T],
+    dst: &mut [T],
+    convolve: impl Fn(&FilterWeights<W>, &[T], &mut [T]) -> Result<(), PicScaleError>,
+) -> Result<(), PicScaleError>
+where
+    T: Copy + Default + Send + Sync,
+{
+    match weights {
+        // Rows are pushed one by one, so fast paths are never taken
+        ResizeWeights::IntegerRatio { general, .. } => {
+            horizontal_row::<T, W, CHANNELS>(general, src, dst, convolve)
+        }
+        ResizeWeights::Nearest { columns, .. } => {
+            resize_nearest::<T, CHANNELS>(
+                src,
+                src.len(),
+                dst,
+                dst.len(),
+                columns,
+                &[Some(0)],
+                &Executor::sequential(),
+            );
+            Ok(())
+        }
+        ResizeWeights::Convolution {
+            horizontal: Some(horizontal),
+            ..
+        } => convolve(horizontal, src, dst),
+        ResizeWeights::Copy
+        | ResizeWeights::Convolution {
+            horizontal: None, ..
+        } => {
+            dst.copy_from_slice(src);
+            Ok(())
+        }
+    }
+}
+
+/// Vertical pass of one destination row for any kind of weights
+fn vertical_row<T, W>(
+    weights: &ResizeWeights<W>,
+    y: usize,
+    bounds: &FilterBounds,
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    handle_column: impl Fn(&FilterBounds, &[T], &mut [T], usize, &[W]),
+) where
+    T: Copy + Default + AntiRingingSample,
+{
+    match weights {
+        ResizeWeights::IntegerRatio { general, .. } => {
+            vertical_row(general, y, bounds, src, src_stride, dst, handle_column)
+        }
+        ResizeWeights::Convolution {
+            vertical: Some(vertical),
+            ..
+        } => {
+            let aligned_size = vertical.aligned_size;
+            let row_weights = &vertical.weights[y * aligned_size..(y + 1) * aligned_size];
+            handle_column(bounds, src, dst, src_stride, row_weights);
+            if let Some(anti_ringing) = &vertical.anti_ringing {
+                anti_ring_column(
+                    bounds,
+                    &anti_ringing.taps[y],
+                    src,
+                    src_stride,
+                    dst,
+                    anti_ringing.strength,
+                );
+            }
+        }
+        _ => {
+            if bounds.size == 0 {
+                dst.fill(T::default());
+            } else {
+                let start = bounds.start * src_stride;
+                dst.copy_from_slice(&src[start..start + dst.len()]);
+            }
+        }
+    }
+}
+
+macro_rules! dispatch_stream_layout {
+    ($self:ident, $method:ident, $($arg:expr),*) => {
+        match $self.plan.layout().channels() {
+            1 => $self.$method::<1>($($arg),*),
+            2 => $self.$method::<2>($($arg),*),
+            3 => $self.$method::<3>($($arg),*),
+            4 => $self.$method::<4>($($arg),*),
+            5 => $self.$method::<5>($($arg),*),
+            6 => $self.$method::<6>($($arg),*),
+            7 => $self.$method::<7>($($arg),*),
+            8 => $self.$method::<8>($($arg),*),
+            channels => Err(PicScaleError::UnsupportedChannelsCount(channels)),
+        }
+    };
+}
+
+macro_rules! impl_streaming {
+    ($ty:ty, $($variant:ident => $convolve_row:ident<$j:ty $(, $f:ty)?>, $column_handler:ident),+) => {
+        impl StreamingResizer<$ty> {
+            /// Creates resizer executing the plan row by row
+            ///
+            /// Returns error for plans with elliptical weighted averaging.
+            pub fn new(plan: ResizePlan<$ty>) -> Result<StreamingResizer<$ty>, PicScaleError> {
+                let state = match plan.weights() {
+                    $(PlanWeights::$variant(weights) => StreamState::new(&plan, weights),)+
+                    #[allow(unreachable_patterns)]
+                    _ => return Err(PicScaleError::UnsupportedStreaming),
+                };
+                Ok(StreamingResizer { plan, state })
+            }
+
+            /// Pushes next source row, destination rows which became available are passed
+            /// into `sink` together with their index, in order
+            ///
+            /// # Arguments
+            ///
+            /// * `source_row`: Source row, must be exactly `width * channels` elements
+            /// * `sink`: Receives destination row index and row of `width * channels` elements
+            ///
+            /// Returns [PicScaleError::TooManyRows] when all source rows are already pushed.
+            ///
+            pub fn push_row(
+                &mut self,
+                source_row: &[$ty],
+                sink: impl FnMut(usize, &[$ty]),
+            ) -> Result<(), PicScaleError> {
+                dispatch_stream_layout!(self, push_row_layout, source_row, sink)
+            }
+
+            fn push_row_layout<const CHANNELS: usize>(
+                &mut self,
+                source_row: &[$ty],
+                sink: impl FnMut(usize, &[$ty]),
+            ) -> Result<(), PicScaleError> {
+                let bit_depth = self.plan.bit_depth();
+                let source_width = self.plan.source_size().width;
+                let destination_width = self.plan.destination_size().width;
+                let premultiply_2 = AlphaTransform::<2> { bit_depth };
+                let premultiply_4 = AlphaTransform::<4> { bit_depth };
+                let transform: Option<&dyn RowTransform<$ty, $ty>> =
+                    match (needs_premultiplication(&self.plan), CHANNELS) {
+                        (true, 2) => Some(&premultiply_2),
+                        (true, 4) => Some(&premultiply_4),
+                        _ => None,
+                    };
+                match self.plan.weights() {
+                    $(PlanWeights::$variant(weights) => self.state.push_row(
+                        source_row,
+                        sink,
+                        |src, dst| {
+                            horizontal_row::<$ty, _, CHANNELS>(weights, src, dst, |filters, src, dst| {
+                                $convolve_row::<$ty, $j, $($f,)? CHANNELS>(
+                                    src,
+                                    src.len(),
+                                    ImageSize::new(source_width, 1),
+                                    filters,
+                                    dst,
+                                    dst.len(),
+                                    ImageSize::new(destination_width, 1),
+                                    bit_depth,
+                                    &Executor::sequential(),
+                                )
+                            })
+                        },
+                        |y, bounds, src, src_stride, dst| {
+                            vertical_row(weights, y, bounds, src, src_stride, dst, |bounds, src, dst, src_stride, weights| {
+                                <$ty as $column_handler<$ty, $j $(, $f)?>>::handle_column(
+                                    bounds, src, dst, src_stride, weights, bit_depth,
+                                )
+                            })
+                        },
+                        transform,
+                    ),)+
+                    #[allow(unreachable_patterns)]
+                    _ => Err(PicScaleError::UnsupportedStreaming),
+                }
+            }
+        }
+    };
+}
+
+impl_streaming!(
+    u8,
+    FixedPoint => convolve_row_fixed_point<i32>, ColumnHandlerFixedPoint
+);
+impl_streaming!(
+    u16,
+    FixedPoint => convolve_row_fixed_point<i32>, ColumnHandlerFixedPoint,
+    FloatingPoint => convolve_row_floating_point<f32, f32>, ColumnHandlerFloatingPoint
+);
+impl_streaming!(
+    f32,
+    FloatingPoint => convolve_row_floating_point<f32, f32>, ColumnHandlerFloatingPoint
+);
+#[cfg(feature = "half")]
+impl_streaming!(
+    half::f16,
+    FloatingPoint => convolve_row_floating_point<f32, f32>, ColumnHandlerFloatingPoint
+);
@@ -0,0 +1,192 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use crate::error::PicScaleError;
+use crate::image_size::ImageSize;
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+/// Default of [crate::ResizePlan::with_min_parallel_work], in pixels
+pub const DEFAULT_MIN_PARALLEL_WORK: usize = 128 * 128;
+
+/// Threads used to execute [crate::ResizePlan]
+///
+/// Without `rayon` feature everything always runs on the calling thread.
+///
+/// Free functions, and linearization helpers such as [crate::image_to_linear],
+/// use [ThreadingPolicy::Global], and so run in the pool they are called from.
+/// Resizing with another policy goes through [crate::ResizePlan::with_threading],
+/// linearization helpers have `_with_threading` variants such as
+/// [crate::image_to_linear_with_threading].
+#[derive(Debug, Clone, Default)]
+pub enum ThreadingPolicy {
+    /// Current rayon pool, which is global one unless called from inside another pool
+    #[default]
+    Global,
+    /// Everything runs on the calling thread
+    Single,
+    /// Dedicated pool with this number of threads, created together with the plan
+    Threads(usize),
+    /// Supplied pool
+    #[cfg(feature = "rayon")]
+    Pool(Arc<rayon::ThreadPool>),
+}
+
+/// Resolved [ThreadingPolicy] together with the parallel work threshold
+#[derive(Debug, Clone)]
+pub(crate) struct Workers {
+    policy: ThreadingPolicy,
+    min_parallel_work: usize,
+    #[cfg(feature = "rayon")]
+    pool: Option<Arc<rayon::ThreadPool>>,
+}
+
+impl Default for Workers {
+    fn default() -> Self {
+        Workers {
+            policy: ThreadingPolicy::Global,
+            min_parallel_work: DEFAULT_MIN_PARALLEL_WORK,
+            #[cfg(feature = "rayon")]
+            pool: None,
+        }
+    }
+}
+
+impl Workers {
+    /// Creates dedicated pool for [ThreadingPolicy::Threads]
+    pub(crate) fn new(policy: ThreadingPolicy) -> Result<Workers, PicScaleError> {
+        #[cfg(feature = "rayon")]
+        let pool = match &policy {
+            ThreadingPolicy::Global | ThreadingPolicy::Single => None,
+            &ThreadingPolicy::Threads(threads) => {
+                if threads == 0 {
+                    return Err(PicScaleError::InvalidThreading);
+                }
+                Some(Arc::new(
+                    rayon::ThreadPoolBuilder::new()
+                        .num_threads(threads)
+                        .build()
+                        .map_err(|_| PicScaleError::InvalidThreading)?,
+                ))
+            }
+            ThreadingPolicy::Pool(pool) => Some(pool.clone()),
+        };
+        #[cfg(not(feature = "rayon"))]
+        if matches!(policy, ThreadingPolicy::Threads(0)) {
+            return Err(PicScaleError::InvalidThreading);
+        }
+        Ok(Workers {
+            policy,
+            #[cfg(feature = "rayon")]
+            pool,
+            ..Workers::default()
+        })
+    }
+
+    pub(crate) fn with_min_parallel_work(self, min_parallel_work: usize) -> Workers {
+        Workers {
+            min_parallel_work,
+            ..self
+        }
+    }
+
+    pub(crate) fn policy(&self) -> &ThreadingPolicy {
+        &self.policy
+    }
+
+    pub(crate) fn min_parallel_work(&self) -> usize {
+        self.min_parallel_work
+    }
+
+    /// Executor for resizing, touching every source and destination pixel
+    pub(crate) fn resize_executor(
+        &self,
+        source_size: ImageSize,
+        destination_size: ImageSize,
+    ) -> Executor {
+        let source_work = source_size.width.saturating_mul(source_size.height);
+        let destination_work = destination_size
+            .width
+            .saturating_mul(destination_size.height);
+        self.executor(source_work.saturating_add(destination_work))
+    }
+
+    /// Executor for a job touching `work` pixels
+    pub(crate) fn executor(&self, work: usize) -> Executor {
+        let single = matches!(
+            self.policy,
+            ThreadingPolicy::Single | ThreadingPolicy::Threads(1)
+        );
+        Executor {
+            parallel: cfg!(feature = "rayon") && !single && work >= self.min_parallel_work,
+            #[cfg(feature = "rayon")]
+            min_task_work: self.min_parallel_work,
+            #[cfg(feature = "rayon")]
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// Decides whether a job runs in parallel, and where
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Executor {
+    parallel: bool,
+    #[cfg(feature = "rayon")]
+    min_task_work: usize,
+    #[cfg(feature = "rayon")]
+    pool: Option<Arc<rayon::ThreadPool>>,
+}
+
+impl Executor {
+    /// Executor running everything on the calling thread
+    pub(crate) fn sequential() -> Executor {
+        Executor::default()
+    }
+
+    /// Always false without `rayon` feature
+    pub(crate) fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Executor {
+    /// Runs `job` inside the selected pool, parallel iterators started from it use that pool
+    pub(crate) fn install<R: Send>(&self, job: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(job),
+            None => job(),
+        }
+    }
+
+    /// Least number of items in one task when every item costs `item_work` pixels
+    pub(crate) fn min_len(&self, item_work: usize) -> usize {
+        (self.min_task_work / item_work.max(1)).max(1)
+    }
+}
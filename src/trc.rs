@@ -219,7 +219,7 @@ pub fn gamma2p8_to_linear(gamma: f32) -> f32 {
 #[inline]
 /// Gamma transfer function for HLG
 pub fn trc_linear(v: f32) -> f32 {
-    v.min(1.).min(0.)
+    v.min(1.).max(0.)
 }
 
 #[inline]
@@ -26,12 +26,34 @@
  * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::error::PicScaleError;
+use crate::threading::{ThreadingPolicy, Workers};
 use crate::TransferFunction;
 #[cfg(feature = "rayon")]
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 #[cfg(feature = "rayon")]
 use rayon::prelude::ParallelSliceMut;
 
+/// Applies `convert` to each pixel, in parallel when image is large enough
+fn convert_pixels<T: Send, const CHANNELS: usize>(
+    in_place: &mut [T],
+    workers: &Workers,
+    convert: impl Fn(&mut [T]) + Send + Sync,
+) {
+    let executor = workers.executor(in_place.len() / CHANNELS);
+    if executor.is_parallel() {
+        #[cfg(feature = "rayon")]
+        executor.install(|| {
+            in_place
+                .par_chunks_exact_mut(CHANNELS)
+                .with_min_len(executor.min_len(1))
+                .for_each(&convert);
+        });
+    } else {
+        in_place.chunks_exact_mut(CHANNELS).for_each(convert);
+    }
+}
+
 /// Converts 8-bit image to linear
 ///
 /// On `CHANNELS` == 2 or `CHANNELS` == 4 alpha will be considered as last item
@@ -42,21 +64,40 @@ use rayon::prelude::ParallelSliceMut;
 /// * `trc` - Transfer function, see [TransferFunction] for more info
 ///
 pub fn image_to_linear<const CHANNELS: usize>(in_place: &mut [u8], trc: TransferFunction) {
+    linearize8::<CHANNELS>(in_place, trc, &Workers::default());
+}
+
+/// Converts 8-bit image to linear with threading policy
+///
+/// Same as [image_to_linear], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn image_to_linear_with_threading<const CHANNELS: usize>(
+    in_place: &mut [u8],
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    linearize8::<CHANNELS>(in_place, trc, &workers);
+    Ok(())
+}
+
+fn linearize8<const CHANNELS: usize>(
+    in_place: &mut [u8],
+    trc: TransferFunction,
+    workers: &Workers,
+) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
     let mut lut_table = [0u8; 256];
     for (i, item) in lut_table.iter_mut().enumerate() {
         *item = (trc.linearize(i as f32 * (1. / 255.0)) * 255.).min(255.) as u8;
     }
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = lut_table[dst[0] as usize];
         } else if CHANNELS == 3 || CHANNELS == 4 {
@@ -77,21 +118,36 @@ pub fn image_to_linear<const CHANNELS: usize>(in_place: &mut [u8], trc: Transfer
 /// * `trc` - Transfer function, see [TransferFunction] for more info
 ///
 pub fn linear_to_gamma_image<const CHANNELS: usize>(in_place: &mut [u8], trc: TransferFunction) {
+    gamma8::<CHANNELS>(in_place, trc, &Workers::default());
+}
+
+/// Converts 8-bit linear image to gamma with threading policy
+///
+/// Same as [linear_to_gamma_image], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn linear_to_gamma_image_with_threading<const CHANNELS: usize>(
+    in_place: &mut [u8],
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    gamma8::<CHANNELS>(in_place, trc, &workers);
+    Ok(())
+}
+
+fn gamma8<const CHANNELS: usize>(in_place: &mut [u8], trc: TransferFunction, workers: &Workers) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
     let mut lut_table = [0u8; 256];
     for (i, item) in lut_table.iter_mut().enumerate() {
         *item = (trc.gamma(i as f32 * (1. / 255.0)) * 255.).min(255.) as u8;
     }
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = lut_table[dst[0] as usize];
         } else if CHANNELS == 3 || CHANNELS == 4 {
@@ -115,6 +171,36 @@ pub fn image16_to_linear16<const CHANNELS: usize>(
     in_place: &mut [u16],
     bit_depth: u32,
     trc: TransferFunction,
+) {
+    linearize16::<CHANNELS>(in_place, bit_depth, trc, &Workers::default());
+}
+
+/// Converts 8-16-bit image to linear with threading policy
+///
+/// Same as [image16_to_linear16], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn image16_to_linear16_with_threading<const CHANNELS: usize>(
+    in_place: &mut [u16],
+    bit_depth: u32,
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    linearize16::<CHANNELS>(in_place, bit_depth, trc, &workers);
+    Ok(())
+}
+
+fn linearize16<const CHANNELS: usize>(
+    in_place: &mut [u16],
+    bit_depth: u32,
+    trc: TransferFunction,
+    workers: &Workers,
 ) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
     assert!((1..=16).contains(&bit_depth), "Bit depth must be 1..=16");
@@ -124,16 +210,7 @@ pub fn image16_to_linear16<const CHANNELS: usize>(
         *item = (trc.linearize(i as f32 * (1. / max_colors as f32)) * max_colors as f32)
             .min(max_colors as f32) as u16;
     }
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = lut_table[dst[0] as usize];
         } else if CHANNELS == 3 || CHANNELS == 4 {
@@ -157,6 +234,36 @@ pub fn linear16_to_gamma_image16<const CHANNELS: usize>(
     in_place: &mut [u16],
     bit_depth: u32,
     trc: TransferFunction,
+) {
+    gamma16::<CHANNELS>(in_place, bit_depth, trc, &Workers::default());
+}
+
+/// Converts 8-16-bit linear image to gamma with threading policy
+///
+/// Same as [linear16_to_gamma_image16], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn linear16_to_gamma_image16_with_threading<const CHANNELS: usize>(
+    in_place: &mut [u16],
+    bit_depth: u32,
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    gamma16::<CHANNELS>(in_place, bit_depth, trc, &workers);
+    Ok(())
+}
+
+fn gamma16<const CHANNELS: usize>(
+    in_place: &mut [u16],
+    bit_depth: u32,
+    trc: TransferFunction,
+    workers: &Workers,
 ) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
     assert!((1..=16).contains(&bit_depth), "Bit depth must be 1..=16");
@@ -166,16 +273,7 @@ pub fn linear16_to_gamma_image16<const CHANNELS: usize>(
         *item = (trc.gamma(i as f32 * (1. / max_colors as f32)) * max_colors as f32)
             .min(max_colors as f32) as u16;
     }
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = lut_table[dst[0] as usize];
         } else if CHANNELS == 3 || CHANNELS == 4 {
@@ -196,17 +294,36 @@ pub fn linear16_to_gamma_image16<const CHANNELS: usize>(
 /// * `trc` - Transfer function, see [TransferFunction] for more info
 ///
 pub fn image_f32_to_linear_f32<const CHANNELS: usize>(in_place: &mut [f32], trc: TransferFunction) {
+    linearize_f32::<CHANNELS>(in_place, trc, &Workers::default());
+}
+
+/// Converts `f32` image to linear with threading policy
+///
+/// Same as [image_f32_to_linear_f32], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn image_f32_to_linear_f32_with_threading<const CHANNELS: usize>(
+    in_place: &mut [f32],
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    linearize_f32::<CHANNELS>(in_place, trc, &workers);
+    Ok(())
+}
+
+fn linearize_f32<const CHANNELS: usize>(
+    in_place: &mut [f32],
+    trc: TransferFunction,
+    workers: &Workers,
+) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = trc.linearize(dst[0]);
         } else if CHANNELS == 3 || CHANNELS == 4 {
@@ -229,18 +346,37 @@ pub fn image_f32_to_linear_f32<const CHANNELS: usize>(in_place: &mut [f32], trc:
 pub fn linear_f32_to_gamma_image_f32<const CHANNELS: usize>(
     in_place: &mut [f32],
     trc: TransferFunction,
+) {
+    gamma_f32::<CHANNELS>(in_place, trc, &Workers::default());
+}
+
+/// Converts `f32` linear image to gamma with threading policy
+///
+/// Same as [linear_f32_to_gamma_image_f32], but executed with `threading` instead of the current rayon pool.
+///
+/// # Arguments
+///
+/// * `in_place`: Where convert to
+/// * `trc` - Transfer function, see [TransferFunction] for more info
+/// * `threading`: Threads executing conversion, see [ThreadingPolicy] for more info
+///
+pub fn linear_f32_to_gamma_image_f32_with_threading<const CHANNELS: usize>(
+    in_place: &mut [f32],
+    trc: TransferFunction,
+    threading: &ThreadingPolicy,
+) -> Result<(), PicScaleError> {
+    let workers = Workers::new(threading.clone())?;
+    gamma_f32::<CHANNELS>(in_place, trc, &workers);
+    Ok(())
+}
+
+fn gamma_f32<const CHANNELS: usize>(
+    in_place: &mut [f32],
+    trc: TransferFunction,
+    workers: &Workers,
 ) {
     assert!(CHANNELS != 0 && CHANNELS <= 4, "Channels must be 1..=4");
-    let iter;
-    #[cfg(feature = "rayon")]
-    {
-        iter = in_place.par_chunks_exact_mut(CHANNELS);
-    }
-    #[cfg(not(feature = "rayon"))]
-    {
-        iter = in_place.chunks_exact_mut(CHANNELS);
-    }
-    iter.for_each(|dst| {
+    convert_pixels::<_, CHANNELS>(in_place, workers, |dst| {
         if CHANNELS == 1 || CHANNELS == 2 {
             dst[0] = trc.gamma(dst[0]);
         } else if CHANNELS == 3 || CHANNELS == 4 {
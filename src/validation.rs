@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::error::{MismatchedSize, PicScaleError};
+use crate::image_size::ImageSize;
+
+/// Checks that slice, stride and size describe a valid source image
+///
+/// Padding after the last row is not required, so slice must hold at least
+/// `stride * (height - 1) + width * CHANNELS` elements.
+///
+/// Returns slice trimmed to the image, so it has exactly `height` rows when split by `stride`.
+pub(crate) fn check_image_layout<T, const CHANNELS: usize>(
+    src: &[T],
+    stride: usize,
+    size: ImageSize,
+) -> Result<&[T], PicScaleError> {
+    let image_length = image_length::<CHANNELS>(stride, size)?;
+    if src.len() < image_length {
+        return Err(PicScaleError::SourceSizeMismatch(MismatchedSize {
+            expected: image_length,
+            received: src.len(),
+        }));
+    }
+    Ok(&src[..image_length])
+}
+
+/// Checks that slice, stride and size describe a valid destination image
+///
+/// Same as [check_image_layout], elements after the image are never written.
+pub(crate) fn check_destination_layout<T, const CHANNELS: usize>(
+    dst: &mut [T],
+    stride: usize,
+    size: ImageSize,
+) -> Result<&mut [T], PicScaleError> {
+    let image_length = image_length::<CHANNELS>(stride, size)?;
+    if dst.len() < image_length {
+        return Err(PicScaleError::DestinationSizeMismatch(MismatchedSize {
+            expected: image_length,
+            received: dst.len(),
+        }));
+    }
+    Ok(&mut dst[..image_length])
+}
+
+/// Least amount of elements holding an image, last row has no padding
+fn image_length<const CHANNELS: usize>(
+    stride: usize,
+    size: ImageSize,
+) -> Result<usize, PicScaleError> {
+    check_layout::<CHANNELS>(stride, size)?;
+    Ok(stride * (size.height - 1) + size.width * CHANNELS)
+}
+
+/// Checks that stride and size describe a valid image
+pub(crate) fn check_layout<const CHANNELS: usize>(
+    stride: usize,
+    size: ImageSize,
+) -> Result<(), PicScaleError> {
+    check_channels::<CHANNELS>()?;
+    if size.width == 0 || size.height == 0 {
+        return Err(PicScaleError::ZeroImageDimensions);
+    }
+    let (row_length, is_stride_overflowed) = size.width.overflowing_mul(CHANNELS);
+    if is_stride_overflowed {
+        return Err(PicScaleError::ImageSizeOverflow);
+    }
+    if stride < row_length {
+        return Err(PicScaleError::InvalidStride(MismatchedSize {
+            expected: row_length,
+            received: stride,
+        }));
+    }
+    let (_, is_size_overflowing) = stride.overflowing_mul(size.height);
+    if is_size_overflowing {
+        return Err(PicScaleError::ImageSizeOverflow);
+    }
+    Ok(())
+}
+
+/// Checks that channels count is supported
+pub(crate) fn check_channels<const CHANNELS: usize>() -> Result<(), PicScaleError> {
+    if CHANNELS == 0 {
+        return Err(PicScaleError::UnsupportedChannelsCount(CHANNELS));
+    }
+    Ok(())
+}
+
+/// Checks that bit-depth is supported for integral images
+pub(crate) fn check_bit_depth(bit_depth: u32) -> Result<(), PicScaleError> {
+    if bit_depth > 16 || bit_depth == 0 {
+        return Err(PicScaleError::UnsupportedBitDepth(bit_depth));
+    }
+    Ok(())
+}
+
+/// Copies image with stride `src_stride` into one with stride `dst_stride`
+pub(crate) fn copy_image<T: Copy, const CHANNELS: usize>(
+    src: &[T],
+    src_stride: usize,
+    dst: &mut [T],
+    dst_stride: usize,
+    size: ImageSize,
+) {
+    let row_length = size.width * CHANNELS;
+    for (dst, src) in dst.chunks_mut(dst_stride).zip(src.chunks(src_stride)) {
+        dst[..row_length].copy_from_slice(&src[..row_length]);
+    }
+}
+
+/// Returns first `size` items of `scratch`, growing it if required
+pub(crate) fn scratch_slice<T: Copy + Default>(scratch: &mut Vec<T>, size: usize) -> &mut [T] {
+    if scratch.len() < size {
+        scratch.resize(size, T::default());
+    }
+    &mut scratch[..size]
+}
+
+/// Computes row length for tightly packed image
+pub(crate) fn packed_stride<const CHANNELS: usize>(
+    size: ImageSize,
+) -> Result<usize, PicScaleError> {
+    size.width
+        .checked_mul(CHANNELS)
+        .ok_or(PicScaleError::ImageSizeOverflow)
+}
@@ -0,0 +1,264 @@
+/*
+ * Copyright (c) Radzivon Bartoshyk, 10/2024. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without modification,
+ * are permitted provided that the following conditions are met:
+ *
+ * 1.  Redistributions of source code must retain the above copyright notice, this
+ * list of conditions and the following disclaimer.
+ *
+ * 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * this list of conditions and the following disclaimer in the documentation
+ * and/or other materials provided with the distribution.
+ *
+ * 3.  Neither the name of the copyright holder nor the names of its
+ * contributors may be used to endorse or promote products derived from
+ * this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::image_size::ImageSize;
+use crate::resize_options::ResizeOptions;
+use crate::scale_transform::ScaleTransform;
+
+/// Chroma subsampling of planar YUV image
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum YuvSubsampling {
+    /// Chroma has half width and half height of luma
+    #[default]
+    Yuv420,
+    /// Chroma has half width and full height of luma
+    Yuv422,
+    /// Chroma has the same size as luma
+    Yuv444,
+}
+
+impl YuvSubsampling {
+    /// Horizontal and vertical subsampling factors
+    pub(crate) fn factors(self) -> (usize, usize) {
+        match self {
+            YuvSubsampling::Yuv420 => (2, 2),
+            YuvSubsampling::Yuv422 => (2, 1),
+            YuvSubsampling::Yuv444 => (1, 1),
+        }
+    }
+
+    /// Size of chroma planes for luma of `luma_size`, odd dimensions are rounded up
+    pub fn chroma_size(self, luma_size: ImageSize) -> ImageSize {
+        let (x_factor, y_factor) = self.factors();
+        ImageSize::new(
+            luma_size.width.div_ceil(x_factor),
+            luma_size.height.div_ceil(y_factor),
+        )
+    }
+}
+
+/// Position of subsampled chroma samples relative to luma samples
+///
+/// Matters only for subsampled directions, for 4:4:4 every siting is the same.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ChromaSiting {
+    /// Chroma is co-sited with even luma columns and centered between luma rows,
+    /// MPEG-2, H.264 and HEVC default (`chroma_sample_loc_type` 0)
+    #[default]
+    Left,
+    /// Chroma is centered between luma samples in both directions,
+    /// JPEG and MPEG-1 (`chroma_sample_loc_type` 1)
+    Center,
+    /// Chroma is co-sited with top-left luma sample,
+    /// BT.2020 and H.264 `chroma_sample_loc_type` 2
+    TopLeft,
+}
+
+impl ChromaSiting {
+    /// Checks whether chroma is co-sited with luma horizontally and vertically
+    fn is_co_sited(self) -> (bool, bool) {
+        match self {
+            ChromaSiting::Left => (true, false),
+            ChromaSiting::Center => (false, false),
+            ChromaSiting::TopLeft => (true, true),
+        }
+    }
+}
+
+/// Planar YUV image, chroma planes are sized according to [YuvSubsampling::chroma_size]
+#[derive(Debug, Copy, Clone)]
+pub struct YuvPlanarImage<'a, T> {
+    pub y_plane: &'a [T],
+    /// Luma row length in elements, must be at least `width`
+    pub y_stride: usize,
+    pub u_plane: &'a [T],
+    /// Chroma row length in elements, must be at least chroma width
+    pub u_stride: usize,
+    pub v_plane: &'a [T],
+    /// Chroma row length in elements, must be at least chroma width
+    pub v_stride: usize,
+    /// Luma size
+    pub size: ImageSize,
+}
+
+/// Mutable planar YUV image, chroma planes are sized according to [YuvSubsampling::chroma_size]
+#[derive(Debug)]
+pub struct YuvPlanarImageMut<'a, T> {
+    pub y_plane: &'a mut [T],
+    /// Luma row length in elements, must be at least `width`
+    pub y_stride: usize,
+    pub u_plane: &'a mut [T],
+    /// Chroma row length in elements, must be at least chroma width
+    pub u_stride: usize,
+    pub v_plane: &'a mut [T],
+    /// Chroma row length in elements, must be at least chroma width
+    pub v_stride: usize,
+    /// Luma size
+    pub size: ImageSize,
+}
+
+/// Semi-planar YUV image, such as NV12 or P010, where chroma is a single plane
+/// of interleaved `UV` or `VU` pairs sized according to [YuvSubsampling::chroma_size]
+#[derive(Debug, Copy, Clone)]
+pub struct YuvSemiPlanarImage<'a, T> {
+    pub y_plane: &'a [T],
+    /// Luma row length in elements, must be at least `width`
+    pub y_stride: usize,
+    pub uv_plane: &'a [T],
+    /// Chroma row length in elements, must be at least `2 * chroma width`
+    pub uv_stride: usize,
+    /// Luma size
+    pub size: ImageSize,
+}
+
+/// Mutable semi-planar YUV image, see [YuvSemiPlanarImage] for more info
+#[derive(Debug)]
+pub struct YuvSemiPlanarImageMut<'a, T> {
+    pub y_plane: &'a mut [T],
+    /// Luma row length in elements, must be at least `width`
+    pub y_stride: usize,
+    pub uv_plane: &'a mut [T],
+    /// Chroma row length in elements, must be at least `2 * chroma width`
+    pub uv_stride: usize,
+    /// Luma size
+    pub size: ImageSize,
+}
+
+/// Placement of high bit-depth samples inside `u16`
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum YuvBitPacking {
+    /// Samples are stored in the low bits, upper bits are zero
+    #[default]
+    Lsb,
+    /// Samples are stored in the high bits, low bits are zero, as in P010 and P210
+    Msb,
+}
+
+/// Chroma sampling along one axis
+#[derive(Debug, Copy, Clone)]
+struct ChromaAxis {
+    factor: usize,
+    co_sited: bool,
+}
+
+impl ChromaAxis {
+    /// Distance of chroma sample from the center of luma samples it covers, in luma pixels
+    ///
+    /// Chroma sample `j` lays at luma position `factor * (j + 0.5) + displacement`.
+    fn displacement(self) -> f64 {
+        if self.co_sited {
+            0.5 - self.factor as f64 / 2.
+        } else {
+            0.
+        }
+    }
+
+    /// Magnification and offset of destination chroma over the source chroma,
+    /// and whether it is the same as stretching chroma plane over the other one
+    fn mapping(
+        source: ChromaAxis,
+        source_luma: usize,
+        destination: ChromaAxis,
+        destination_luma: usize,
+    ) -> (f64, f64, bool) {
+        let ratio = source_luma as f64 / destination_luma as f64;
+        let source_factor = source.factor as f64;
+        let scale = ratio * destination.factor as f64 / source_factor;
+        let offset = (ratio * destination.displacement() - source.displacement()) / source_factor;
+        // Chroma of odd sized images is rounded up, so its ratio may drift from luma one
+        let stretched = source_luma.div_ceil(source.factor) * destination_luma * source.factor
+            == destination_luma.div_ceil(destination.factor) * source_luma * destination.factor;
+        (1. / scale, offset, stretched && offset == 0.)
+    }
+}
+
+/// Subsampling and siting of chroma planes
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ChromaFormat {
+    pub subsampling: YuvSubsampling,
+    pub siting: ChromaSiting,
+}
+
+impl ChromaFormat {
+    pub fn new(subsampling: YuvSubsampling, siting: ChromaSiting) -> ChromaFormat {
+        ChromaFormat {
+            subsampling,
+            siting,
+        }
+    }
+
+    /// Horizontal and vertical sampling
+    fn axes(self) -> (ChromaAxis, ChromaAxis) {
+        let (x_factor, y_factor) = self.subsampling.factors();
+        let (x_co_sited, y_co_sited) = self.siting.is_co_sited();
+        (
+            ChromaAxis {
+                factor: x_factor,
+                co_sited: x_co_sited,
+            },
+            ChromaAxis {
+                factor: y_factor,
+                co_sited: y_co_sited,
+            },
+        )
+    }
+}
+
+/// Options of chroma planes, destination chroma is placed over the source chroma
+/// with [ScaleTransform] unless it is just stretched over the source one
+pub(crate) fn chroma_resize_options(
+    source_size: ImageSize,
+    source_format: ChromaFormat,
+    destination_size: ImageSize,
+    destination_format: ChromaFormat,
+    options: ResizeOptions,
+) -> ResizeOptions {
+    let (source_x, source_y) = source_format.axes();
+    let (destination_x, destination_y) = destination_format.axes();
+    let (scale_x, offset_x, stretched_x) = ChromaAxis::mapping(
+        source_x,
+        source_size.width,
+        destination_x,
+        destination_size.width,
+    );
+    let (scale_y, offset_y, stretched_y) = ChromaAxis::mapping(
+        source_y,
+        source_size.height,
+        destination_y,
+        destination_size.height,
+    );
+    if stretched_x && stretched_y {
+        return options;
+    }
+    options.with_scale_transform(ScaleTransform::new(
+        scale_x as f32,
+        scale_y as f32,
+        offset_x as f32,
+        offset_y as f32,
+    ))
+}
@@ -0,0 +1,34 @@
+use pic_scale_safe::{ChannelLayout, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan};
+
+/// Vertical step edge upscaled with Lanczos overshoots around the step
+fn resize_step(anti_ringing: f32) -> Vec<f32> {
+    let source_size = ImageSize::new(16, 4);
+    let destination_size = ImageSize::new(53, 4);
+    let source = (0..source_size.width * source_size.height)
+        .map(|i| if i % source_size.width < 8 { 0.1 } else { 0.9 })
+        .collect::<Vec<f32>>();
+    let plan = ResizePlan::<f32>::with_options(
+        source_size,
+        destination_size,
+        ChannelLayout::Plane,
+        ResizeOptions::new(ResamplingFunction::Lanczos3).with_anti_ringing(anti_ringing),
+    )
+    .unwrap();
+    let mut destination = vec![0f32; destination_size.width * destination_size.height];
+    plan.resize(&source, &mut destination).unwrap();
+    destination
+}
+
+#[test]
+fn anti_ringing_clamps_overshoot() {
+    let ringing = resize_step(0.);
+    assert!(ringing.iter().any(|&v| !(0.1..=0.9).contains(&v)));
+    let clamped = resize_step(1.);
+    for &v in clamped.iter() {
+        assert!((0.1 - 1e-6..=0.9 + 1e-6).contains(&v), "{v}");
+    }
+    // Flat areas far from the step are untouched
+    for (c, r) in clamped[..5].iter().zip(ringing[..5].iter()) {
+        assert!((c - r).abs() < 1e-6, "{c} != {r}");
+    }
+}
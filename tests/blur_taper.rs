@@ -0,0 +1,97 @@
+use pic_scale_safe::{
+    ChannelLayout, ImageSize, PicScaleError, ResamplingFunction, ResizeOptions, ResizePlan,
+};
+
+const SOURCE: ImageSize = ImageSize {
+    width: 16,
+    height: 1,
+};
+
+const DESTINATION: ImageSize = ImageSize {
+    width: 64,
+    height: 1,
+};
+
+fn resize(source: &[f32], options: ResizeOptions) -> Result<Vec<f32>, PicScaleError> {
+    let plan = ResizePlan::<f32>::with_options(SOURCE, DESTINATION, ChannelLayout::Plane, options)?;
+    let mut destination = vec![0f32; DESTINATION.width];
+    plan.resize(source, &mut destination)?;
+    Ok(destination)
+}
+
+fn impulse() -> Vec<f32> {
+    let mut impulse = vec![0f32; SOURCE.width];
+    impulse[8] = 1.;
+    impulse
+}
+
+/// Destination samples reached by the impulse
+fn spread(resized: &[f32]) -> usize {
+    resized.iter().filter(|v| v.abs() > 1e-6).count()
+}
+
+/// Samples near the peak, flat part of the kernel shows up here
+fn plateau(resized: &[f32]) -> usize {
+    let peak = resized.iter().copied().fold(f32::MIN, f32::max);
+    resized.iter().filter(|&&v| v >= peak * 0.98).count()
+}
+
+#[test]
+fn blur_widens_kernel() {
+    let options = ResizeOptions::new(ResamplingFunction::Bilinear);
+    let sharp = resize(&impulse(), options.with_blur(0.5)).unwrap();
+    let plain = resize(&impulse(), options).unwrap();
+    let soft = resize(&impulse(), options.with_blur(2.)).unwrap();
+    assert!(spread(&sharp) < spread(&plain), "{sharp:?}\n{plain:?}");
+    assert!(spread(&plain) < spread(&soft), "{plain:?}\n{soft:?}");
+}
+
+#[test]
+fn taper_flattens_kernel_center() {
+    let options = ResizeOptions::new(ResamplingFunction::Gaussian);
+    let plain = resize(&impulse(), options).unwrap();
+    let tapered = resize(&impulse(), options.with_taper(0.5)).unwrap();
+    assert!(
+        plateau(&plain) < plateau(&tapered),
+        "{plain:?}\n{tapered:?}"
+    );
+}
+
+/// Weights are normalized whatever the shape is
+#[test]
+fn shaped_kernels_keep_flat_image() {
+    let source = vec![0.25f32; SOURCE.width];
+    for (blur, taper) in [
+        (0.5, 0.),
+        (2., 0.3),
+        (ResizeOptions::MAX_BLUR, ResizeOptions::MAX_TAPER),
+    ] {
+        let options = ResizeOptions::new(ResamplingFunction::Lanczos3)
+            .with_blur(blur)
+            .with_taper(taper);
+        let resized = resize(&source, options).unwrap();
+        assert!(
+            resized.iter().all(|v| (v - 0.25).abs() < 1e-5),
+            "blur {blur}, taper {taper}: {resized:?}"
+        );
+    }
+}
+
+#[test]
+fn blur_and_taper_are_bounded() {
+    let options = ResizeOptions::new(ResamplingFunction::Lanczos3);
+    for blur in [0., -1., 16.5, f32::INFINITY, f32::NAN] {
+        assert_eq!(
+            resize(&impulse(), options.with_blur(blur)).err(),
+            Some(PicScaleError::InvalidResamplingFunction),
+            "blur {blur}"
+        );
+    }
+    for taper in [-0.1, 0.95, 1., f32::NAN] {
+        assert_eq!(
+            resize(&impulse(), options.with_taper(taper)).err(),
+            Some(PicScaleError::InvalidResamplingFunction),
+            "taper {taper}"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use pic_scale_safe::{
+    resample_chroma16, resample_chroma8, ChromaFormat, ChromaSiting, ImageSize, ResamplingFunction,
+    YuvSubsampling,
+};
+
+const LUMA: ImageSize = ImageSize {
+    width: 64,
+    height: 48,
+};
+
+/// Smooth chroma plane of 4:4:4 image, which survives subsampling
+fn smooth(amplitude: f64, middle: f64) -> Vec<f64> {
+    (0..LUMA.height)
+        .flat_map(|y| {
+            (0..LUMA.width).map(move |x| {
+                let phase_x = x as f64 / LUMA.width as f64 * std::f64::consts::TAU;
+                let phase_y = y as f64 / LUMA.height as f64 * std::f64::consts::TAU;
+                middle + amplitude * phase_x.sin() * phase_y.cos()
+            })
+        })
+        .collect()
+}
+
+/// Interior of the plane, edges are shrunk by the kernel both ways
+fn interior(plane: &[u16]) -> impl Iterator<Item = (usize, u16)> + '_ {
+    plane.iter().copied().enumerate().filter(|&(i, _)| {
+        let (x, y) = (i % LUMA.width, i / LUMA.width);
+        (4..LUMA.width - 4).contains(&x) && (4..LUMA.height - 4).contains(&y)
+    })
+}
+
+/// Subsampling and upsampling back keeps smooth chroma for every siting
+#[test]
+fn chroma_round_trip_keeps_smooth_plane() {
+    let full = ChromaFormat::new(YuvSubsampling::Yuv444, ChromaSiting::Center);
+    for subsampling in [YuvSubsampling::Yuv420, YuvSubsampling::Yuv422] {
+        for siting in [
+            ChromaSiting::Left,
+            ChromaSiting::Center,
+            ChromaSiting::TopLeft,
+        ] {
+            let format = ChromaFormat::new(subsampling, siting);
+            let chroma_size = subsampling.chroma_size(LUMA);
+
+            let source8 = smooth(60., 128.)
+                .iter()
+                .map(|&v| v.round() as u8)
+                .collect::<Vec<_>>();
+            let subsampled =
+                resample_chroma8(&source8, LUMA, full, format, ResamplingFunction::CatmullRom)
+                    .unwrap();
+            assert_eq!(subsampled.len(), chroma_size.width * chroma_size.height);
+            let restored = resample_chroma8(
+                &subsampled,
+                LUMA,
+                format,
+                full,
+                ResamplingFunction::CatmullRom,
+            )
+            .unwrap();
+            let restored = restored.iter().map(|&v| v as u16).collect::<Vec<_>>();
+            for (i, v) in interior(&restored) {
+                assert!(
+                    v.abs_diff(source8[i] as u16) <= 3,
+                    "{format:?} {i}: {v} != {}",
+                    source8[i]
+                );
+            }
+
+            let source16 = smooth(240., 512.)
+                .iter()
+                .map(|&v| v.round() as u16)
+                .collect::<Vec<_>>();
+            let subsampled = resample_chroma16(
+                &source16,
+                LUMA,
+                full,
+                format,
+                10,
+                ResamplingFunction::CatmullRom,
+            )
+            .unwrap();
+            let restored = resample_chroma16(
+                &subsampled,
+                LUMA,
+                format,
+                full,
+                10,
+                ResamplingFunction::CatmullRom,
+            )
+            .unwrap();
+            for (i, v) in interior(&restored) {
+                assert!(
+                    v.abs_diff(source16[i]) <= 12,
+                    "{format:?} {i}: {v} != {}",
+                    source16[i]
+                );
+            }
+        }
+    }
+}
+
+/// Conversion into the same format doesn't touch the plane
+#[test]
+fn same_format_is_copied() {
+    let format = ChromaFormat::new(YuvSubsampling::Yuv420, ChromaSiting::Left);
+    let chroma_size = YuvSubsampling::Yuv420.chroma_size(LUMA);
+    let source = (0..chroma_size.width * chroma_size.height)
+        .map(|i| (i * 13 % 256) as u8)
+        .collect::<Vec<_>>();
+    let resampled =
+        resample_chroma8(&source, LUMA, format, format, ResamplingFunction::Lanczos3).unwrap();
+    assert_eq!(resampled, source);
+}
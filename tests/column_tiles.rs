@@ -0,0 +1,88 @@
+use pic_scale_safe::{
+    resize_plane_f32, resize_rgb16, resize_rgb8, resize_rgba8, ImageSize, ResamplingFunction,
+};
+
+/// Wide enough for the column pass to be split into strips
+const WIDTH: usize = 3001;
+const SLICE: usize = 97;
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 89 % 256) as u8).collect()
+}
+
+/// Column pass is independent for every column, so resizing narrow slices one by one,
+/// which are never split into strips, must give the same result as resizing the whole image
+fn assert_matches_slices<T: Copy + PartialEq + std::fmt::Debug>(
+    source: &[T],
+    channels: usize,
+    source_height: usize,
+    destination_height: usize,
+    resize: impl Fn(&[T], ImageSize, ImageSize) -> Vec<T>,
+) {
+    let row = WIDTH * channels;
+    let whole = resize(
+        source,
+        ImageSize::new(WIDTH, source_height),
+        ImageSize::new(WIDTH, destination_height),
+    );
+    for x in (0..WIDTH).step_by(SLICE) {
+        let width = SLICE.min(WIDTH - x);
+        let slice = source
+            .chunks_exact(row)
+            .flat_map(|r| r[x * channels..(x + width) * channels].iter().copied())
+            .collect::<Vec<_>>();
+        let resized = resize(
+            &slice,
+            ImageSize::new(width, source_height),
+            ImageSize::new(width, destination_height),
+        );
+        for (y, resized_row) in resized.chunks_exact(width * channels).enumerate() {
+            let whole_row = &whole[y * row + x * channels..y * row + (x + width) * channels];
+            assert_eq!(resized_row, whole_row, "row {y}, columns from {x}");
+        }
+    }
+}
+
+#[test]
+fn rgb8_strips_match_narrow_slices() {
+    for destination_height in [9, 13] {
+        assert_matches_slices(
+            &pattern(WIDTH * 121 * 3),
+            3,
+            121,
+            destination_height,
+            |s, a, b| resize_rgb8(s, a, b, ResamplingFunction::Lanczos3).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn rgba8_strips_match_narrow_slices() {
+    assert_matches_slices(&pattern(WIDTH * 121 * 4), 4, 121, 9, |s, a, b| {
+        resize_rgba8(s, a, b, ResamplingFunction::Lanczos3).unwrap()
+    });
+}
+
+#[test]
+fn rgb16_strips_match_narrow_slices() {
+    for bit_depth in [10, 16] {
+        let source = pattern(WIDTH * 121 * 3)
+            .iter()
+            .map(|&v| (v as u16) << (bit_depth - 8))
+            .collect::<Vec<_>>();
+        assert_matches_slices(&source, 3, 121, 9, |s, a, b| {
+            resize_rgb16(s, a, b, bit_depth, ResamplingFunction::Lanczos3).unwrap()
+        });
+    }
+}
+
+#[test]
+fn plane_f32_strips_match_narrow_slices() {
+    let source = pattern(WIDTH * 121)
+        .iter()
+        .map(|&v| v as f32 / 255.)
+        .collect::<Vec<_>>();
+    assert_matches_slices(&source, 1, 121, 7, |s, a, b| {
+        resize_plane_f32(s, a, b, ResamplingFunction::Lanczos3).unwrap()
+    });
+}
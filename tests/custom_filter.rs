@@ -0,0 +1,85 @@
+use pic_scale_safe::{
+    resize_plane_f32, CustomFilter, CustomWindow, ImageSize, PicScaleError, ResamplingFunction,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn triangle(x: f32) -> f32 {
+    (1. - x.abs()).max(0.)
+}
+
+fn hash(function: &ResamplingFunction) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    function.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Custom triangle filter is the same as bilinear one
+#[test]
+fn custom_triangle_matches_bilinear() {
+    let source = (0..23 * 5)
+        .map(|i| (i * 37 % 101) as f32 / 100.)
+        .collect::<Vec<_>>();
+    let resize = |function| {
+        resize_plane_f32(
+            &source,
+            ImageSize::new(23, 5),
+            ImageSize::new(9, 11),
+            function,
+        )
+        .unwrap()
+    };
+    let expected = resize(ResamplingFunction::Bilinear);
+    let resized = resize(ResamplingFunction::Custom(CustomFilter::new(
+        1, triangle, 1.,
+    )));
+    for (&resized, &expected) in resized.iter().zip(expected.iter()) {
+        assert!((resized - expected).abs() < 1e-5, "{resized} != {expected}");
+    }
+}
+
+#[test]
+fn radius_is_bounded() {
+    let source = vec![0f32; 16];
+    for radius in [0., -1., 64.5, f32::INFINITY, f32::NAN] {
+        let filter = CustomFilter::new(1, triangle, radius);
+        assert_eq!(
+            resize_plane_f32(
+                &source,
+                ImageSize::new(4, 4),
+                ImageSize::new(2, 2),
+                ResamplingFunction::Custom(filter),
+            ),
+            Err(PicScaleError::InvalidResamplingFunction),
+            "{radius}"
+        );
+    }
+    let filter = CustomFilter::new(1, triangle, 64.);
+    assert!(resize_plane_f32(
+        &source,
+        ImageSize::new(4, 4),
+        ImageSize::new(2, 2),
+        ResamplingFunction::Custom(filter),
+    )
+    .is_ok());
+}
+
+/// Filters are told apart by id and parameters, not by function addresses
+#[test]
+fn filters_are_compared_by_id() {
+    let filter = ResamplingFunction::Custom(CustomFilter::new(1, triangle, 1.));
+    let same = ResamplingFunction::Custom(CustomFilter::new(1, |x| (1. - x.abs()).max(0.), 1.));
+    assert_eq!(filter, same);
+    assert_eq!(hash(&filter), hash(&same));
+
+    let other_id = ResamplingFunction::Custom(CustomFilter::new(2, triangle, 1.));
+    assert_ne!(filter, other_id);
+    assert!(filter < other_id);
+
+    let windowed = ResamplingFunction::Custom(
+        CustomFilter::new(1, triangle, 1.).with_window(CustomWindow::new(triangle, 1.)),
+    );
+    assert_ne!(filter, windowed);
+    let other_radius = ResamplingFunction::Custom(CustomFilter::new(1, triangle, 2.));
+    assert_ne!(filter, other_radius);
+}
@@ -0,0 +1,92 @@
+use pic_scale_safe::{
+    ChannelLayout, EdgeMode, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan,
+    ScaleTransform,
+};
+
+fn resize_row(edge_mode: EdgeMode) -> Vec<f32> {
+    let source = (1..=12).map(|v| v as f32).collect::<Vec<_>>();
+    let plan = ResizePlan::<f32>::with_options(
+        ImageSize::new(12, 1),
+        ImageSize::new(3, 1),
+        ChannelLayout::Plane,
+        ResizeOptions::new(ResamplingFunction::Bilinear).with_edge_mode(edge_mode),
+    )
+    .unwrap();
+    let mut destination = vec![0f32; 3];
+    plan.resize(&source, &mut destination).unwrap();
+    destination
+}
+
+/// Row `1..=12` downscaled 4 times has bilinear taps `1, 3, 5, 7, 7, 5, 3, 1` / 32
+/// centered between 2nd and 3rd pixel, so the first two taps of the first pixel
+/// fall outside the image and edge mode decides what they read
+#[test]
+fn edge_mode_decides_border_taps() {
+    // Taps inside the image are `5 * 1 + 7 * 2 + 7 * 3 + 5 * 4 + 3 * 5 + 1 * 6 = 81`
+    for (edge_mode, expected) in [
+        (EdgeMode::Shrink, 81. / 28.),
+        // `aa|abc`
+        (EdgeMode::Clamp, (81. + 1. + 3.) / 32.),
+        // `ba|abc`
+        (EdgeMode::Reflect, (81. + 2. + 3.) / 32.),
+        // `kl|abc`
+        (EdgeMode::Wrap, (81. + 11. + 3. * 12.) / 32.),
+        // `00|abc`
+        (EdgeMode::Transparent, 81. / 32.),
+    ] {
+        let resized = resize_row(edge_mode);
+        assert!(
+            (resized[0] - expected).abs() < 1e-5,
+            "{edge_mode:?}: {} != {expected}",
+            resized[0]
+        );
+        // The middle pixel doesn't reach edges
+        assert!((resized[1] - 6.5).abs() < 1e-5, "{edge_mode:?}");
+    }
+}
+
+/// Kernel placed far outside the image reads only edge pixels and padding doesn't grow with offset
+#[test]
+fn far_offset_is_folded_to_image() {
+    let source = (1..=12).map(|v| v as f32).collect::<Vec<_>>();
+    for (offset, edge_mode, expected) in [
+        (1e7, EdgeMode::Clamp, Some(12.)),
+        (-1e7, EdgeMode::Clamp, Some(1.)),
+        (1e6, EdgeMode::Reflect, None),
+        (-1e6, EdgeMode::Wrap, None),
+    ] {
+        let plan = ResizePlan::<f32>::with_options(
+            ImageSize::new(12, 1),
+            ImageSize::new(3, 1),
+            ChannelLayout::Plane,
+            ResizeOptions::new(ResamplingFunction::Bilinear)
+                .with_edge_mode(edge_mode)
+                .with_scale_transform(ScaleTransform::new(0.25, 1., offset, 0.)),
+        )
+        .unwrap();
+        let mut destination = vec![0f32; 3];
+        plan.resize(&source, &mut destination).unwrap();
+        for &v in destination.iter() {
+            match expected {
+                Some(expected) => assert!((v - expected).abs() < 1e-5, "{edge_mode:?}: {v}"),
+                None => assert!((1. - 1e-5..=12. + 1e-5).contains(&v), "{edge_mode:?}: {v}"),
+            }
+        }
+    }
+    // Taps are not precise that far in `f32`, but resizing must not allocate padding up to there
+    for edge_mode in [EdgeMode::Clamp, EdgeMode::Reflect, EdgeMode::Wrap] {
+        for offset in [1e9, -1e9] {
+            let plan = ResizePlan::<u8>::with_options(
+                ImageSize::new(12, 12),
+                ImageSize::new(3, 3),
+                ChannelLayout::Plane,
+                ResizeOptions::new(ResamplingFunction::Lanczos3)
+                    .with_edge_mode(edge_mode)
+                    .with_scale_transform(ScaleTransform::new(0.25, 0.25, offset, offset)),
+            )
+            .unwrap();
+            let mut destination = vec![0u8; 9];
+            plan.resize(&[7u8; 144], &mut destination).unwrap();
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use pic_scale_safe::{
+    ChannelLayout, EdgeMode, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan,
+};
+
+fn polar_options() -> Vec<ResizeOptions> {
+    let mut options = Vec::new();
+    for function in [
+        ResamplingFunction::Lanczos2Jinc,
+        ResamplingFunction::Lanczos3Jinc,
+        ResamplingFunction::Lanczos4Jinc,
+        ResamplingFunction::Ginseng,
+        ResamplingFunction::HaasnSoft,
+    ] {
+        for edge_mode in [EdgeMode::Shrink, EdgeMode::Clamp, EdgeMode::Reflect] {
+            options.push(
+                ResizeOptions::new(function)
+                    .with_ewa(true)
+                    .with_edge_mode(edge_mode),
+            );
+        }
+    }
+    options
+}
+
+fn sizes() -> [(ImageSize, ImageSize); 3] {
+    [
+        (ImageSize::new(37, 29), ImageSize::new(13, 11)),
+        (ImageSize::new(13, 11), ImageSize::new(41, 30)),
+        (ImageSize::new(24, 24), ImageSize::new(31, 9)),
+    ]
+}
+
+#[test]
+fn ewa_keeps_flat_u8_image() {
+    let pixel = [17u8, 128, 250, 255];
+    for (source_size, destination_size) in sizes() {
+        for options in polar_options() {
+            let plan = ResizePlan::<u8>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Rgba,
+                options,
+            )
+            .unwrap();
+            let source = pixel.repeat(source_size.width * source_size.height);
+            let mut destination = vec![0u8; destination_size.width * destination_size.height * 4];
+            plan.resize(&source, &mut destination).unwrap();
+            for (i, resized) in destination.chunks_exact(4).enumerate() {
+                assert_eq!(resized, pixel, "pixel {i}, {options:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn ewa_keeps_flat_f32_image() {
+    for (source_size, destination_size) in sizes() {
+        for options in polar_options() {
+            let plan = ResizePlan::<f32>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                options,
+            )
+            .unwrap();
+            let source = vec![0.375f32; source_size.width * source_size.height];
+            let mut destination = vec![0f32; destination_size.width * destination_size.height];
+            plan.resize(&source, &mut destination).unwrap();
+            for (i, &v) in destination.iter().enumerate() {
+                assert!((v - 0.375).abs() < 1e-5, "pixel {i}: {v}, {options:?}");
+            }
+        }
+    }
+}
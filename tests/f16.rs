@@ -0,0 +1,63 @@
+#![cfg(feature = "half")]
+
+use half::f16;
+use pic_scale_safe::{
+    resize_plane_f16, resize_plane_f32, resize_rgba_f16, resize_rgba_f32, ImageSize,
+    ResamplingFunction,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 2] {
+    [
+        (ImageSize::new(41, 33), ImageSize::new(17, 52)),
+        (ImageSize::new(20, 26), ImageSize::new(55, 13)),
+    ]
+}
+
+fn pattern(size: ImageSize, channels: usize) -> Vec<f32> {
+    (0..size.width * size.height * channels)
+        .map(|i| (i * 37 % 101) as f32 / 100.)
+        .collect()
+}
+
+fn assert_close(actual: &[f16], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (&a, &e)) in actual.iter().zip(expected.iter()).enumerate() {
+        // Source is already rounded to f16, so only storing the result adds an error
+        assert!((a.to_f32() - e).abs() < 1e-3, "{i}: {a} != {e}");
+    }
+}
+
+/// Half precision is resampled in f32, so it differs from f32 only by storage rounding
+#[test]
+fn f16_matches_f32() {
+    for (source_size, destination_size) in sizes() {
+        for function in [ResamplingFunction::Lanczos3, ResamplingFunction::Bilinear] {
+            let source = pattern(source_size, 4)
+                .iter()
+                .map(|&v| f16::from_f32(v))
+                .collect::<Vec<_>>();
+            let widened = source.iter().map(|v| v.to_f32()).collect::<Vec<_>>();
+            let resized =
+                resize_rgba_f16(&source, source_size, destination_size, function).unwrap();
+            let expected =
+                resize_rgba_f32(&widened, source_size, destination_size, function).unwrap();
+            assert_close(&resized, &expected);
+
+            let resized = resize_plane_f16(
+                &source[..source_size.width * source_size.height],
+                source_size,
+                destination_size,
+                function,
+            )
+            .unwrap();
+            let expected = resize_plane_f32(
+                &widened[..source_size.width * source_size.height],
+                source_size,
+                destination_size,
+                function,
+            )
+            .unwrap();
+            assert_close(&resized, &expected);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use pic_scale_safe::{
+    ChannelLayout, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan, ScaleTransform,
+};
+
+const SOURCE: ImageSize = ImageSize {
+    width: 48,
+    height: 36,
+};
+
+fn pattern(size: ImageSize, channels: usize) -> Vec<u8> {
+    (0..size.width * size.height * channels)
+        .map(|i| (i * 97 % 256) as u8)
+        .collect()
+}
+
+/// Same placement as plain stretching, but always goes through kernel weights
+fn general(options: ResizeOptions, source: ImageSize, destination: ImageSize) -> ResizeOptions {
+    options.with_scale_transform(ScaleTransform::new(
+        destination.width as f32 / source.width as f32,
+        destination.height as f32 / source.height as f32,
+        0.,
+        0.,
+    ))
+}
+
+fn resize8(options: ResizeOptions, destination_size: ImageSize) -> Vec<u8> {
+    let plan =
+        ResizePlan::<u8>::with_options(SOURCE, destination_size, ChannelLayout::Rgba, options)
+            .unwrap();
+    let mut destination = vec![0u8; destination_size.width * destination_size.height * 4];
+    plan.resize(&pattern(SOURCE, 4), &mut destination).unwrap();
+    destination
+}
+
+#[test]
+fn area_downscale_matches_general_path() {
+    let destination_size = ImageSize::new(12, 12);
+    let options = ResizeOptions::new(ResamplingFunction::Area);
+    let fast = resize8(options, destination_size);
+    let reference = resize8(general(options, SOURCE, destination_size), destination_size);
+    for (i, (&a, &b)) in fast.iter().zip(reference.iter()).enumerate() {
+        assert!(a.abs_diff(b) <= 1, "sample {i}: {a} != {b}");
+    }
+}
+
+#[test]
+fn area_downscale_f32_matches_general_path() {
+    let destination_size = ImageSize::new(16, 9);
+    let source = pattern(SOURCE, 1)
+        .iter()
+        .map(|&v| v as f32 / 255.)
+        .collect::<Vec<_>>();
+    let resize = |options| {
+        let plan = ResizePlan::<f32>::with_options(
+            SOURCE,
+            destination_size,
+            ChannelLayout::Plane,
+            options,
+        )
+        .unwrap();
+        let mut destination = vec![0f32; destination_size.width * destination_size.height];
+        plan.resize(&source, &mut destination).unwrap();
+        destination
+    };
+    let options = ResizeOptions::new(ResamplingFunction::Area);
+    let fast = resize(options);
+    let reference = resize(general(options, SOURCE, destination_size));
+    for (i, (&a, &b)) in fast.iter().zip(reference.iter()).enumerate() {
+        assert!((a - b).abs() < 1e-5, "sample {i}: {a} != {b}");
+    }
+}
+
+#[test]
+fn nearest_upscale_matches_general_path() {
+    let destination_size = ImageSize::new(96, 108);
+    let options = ResizeOptions::new(ResamplingFunction::Nearest);
+    let fast = resize8(options, destination_size);
+    let reference = resize8(general(options, SOURCE, destination_size), destination_size);
+    assert_eq!(fast, reference);
+}
+
+#[test]
+fn area_downscale_respects_blur() {
+    let destination_size = ImageSize::new(12, 12);
+    let plain = resize8(
+        ResizeOptions::new(ResamplingFunction::Area),
+        destination_size,
+    );
+    let options = ResizeOptions::new(ResamplingFunction::Area).with_blur(2.);
+    let blurred = resize8(options, destination_size);
+    let reference = resize8(general(options, SOURCE, destination_size), destination_size);
+    assert_eq!(blurred, reference);
+    assert_ne!(blurred, plain);
+}
@@ -0,0 +1,185 @@
+use pic_scale_safe::{
+    resize_plane16_fields, resize_plane16_interlaced, resize_plane8_fields,
+    resize_plane8_interlaced, ChannelLayout, ImageSize, PicScaleError, ResamplingFunction,
+    ResizeOptions, ResizePlan, ScaleTransform,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 4] {
+    [
+        (ImageSize::new(24, 48), ImageSize::new(30, 20)),
+        (ImageSize::new(16, 20), ImageSize::new(16, 52)),
+        (ImageSize::new(20, 36), ImageSize::new(12, 36)),
+        (ImageSize::new(18, 30), ImageSize::new(18, 22)),
+    ]
+}
+
+/// Rows of the field starting at frame row `skip`
+fn field_rows<T: Copy>(frame: &[T], size: ImageSize, skip: usize) -> Vec<T> {
+    frame
+        .chunks_exact(size.width)
+        .skip(skip)
+        .step_by(2)
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Scale transform placing destination field over the source one,
+/// found by mapping destination field rows through frame rows
+fn field_transform(source: ImageSize, destination: ImageSize, skip: usize) -> ScaleTransform {
+    let ratio = source.height as f64 / destination.height as f64;
+    // Field row `i` is centered at frame row `2 * i + skip + 0.5`
+    let source_row =
+        |i: usize| ((2. * i as f64 + skip as f64 + 0.5) * ratio - skip as f64 - 0.5) / 2. + 0.5;
+    let step = source_row(1) - source_row(0);
+    ScaleTransform::new(
+        destination.width as f32 / source.width as f32,
+        (1. / step) as f32,
+        0.,
+        (source_row(0) - step / 2.) as f32,
+    )
+}
+
+/// Each field is resampled on its own and placed by frame geometry
+#[test]
+fn fields_follow_frame_geometry() {
+    for (source_size, destination_size) in sizes() {
+        let source = (0..source_size.width * source_size.height)
+            .map(|i| (i * 4099 % 65536) as u16)
+            .collect::<Vec<_>>();
+        let (top, bottom) = resize_plane16_fields(
+            &source,
+            source_size,
+            destination_size,
+            16,
+            ResamplingFunction::Bilinear,
+        )
+        .unwrap();
+        for (skip, resized) in [(0, &top), (1, &bottom)] {
+            let field = |size: ImageSize| ImageSize::new(size.width, (size.height + 1 - skip) / 2);
+            let mut options = ResizeOptions::new(ResamplingFunction::Bilinear);
+            if source_size.height != destination_size.height {
+                options = options.with_scale_transform(field_transform(
+                    source_size,
+                    destination_size,
+                    skip,
+                ));
+            }
+            let plan = ResizePlan::<u16>::with_options(
+                field(source_size),
+                field(destination_size),
+                ChannelLayout::Plane,
+                16,
+                options,
+            )
+            .unwrap();
+            let mut expected = vec![0u16; resized.len()];
+            plan.resize(&field_rows(&source, source_size, skip), &mut expected)
+                .unwrap();
+            for (i, (&resized, &expected)) in resized.iter().zip(expected.iter()).enumerate() {
+                assert!(
+                    resized.abs_diff(expected) <= 1,
+                    "field {skip}, {source_size:?} -> {destination_size:?}, \
+                     {i}: {resized} != {expected}"
+                );
+            }
+        }
+    }
+}
+
+/// Flat fields stay flat, so nothing leaks from one field into the other
+#[test]
+fn fields_are_not_mixed() {
+    for (source_size, destination_size) in sizes() {
+        let source = (0..source_size.height)
+            .flat_map(|y| {
+                let value = if y % 2 == 0 { 1000u16 } else { 50000 };
+                vec![value; source_size.width]
+            })
+            .collect::<Vec<_>>();
+        let resized = resize_plane16_interlaced(
+            &source,
+            source_size,
+            destination_size,
+            16,
+            ResamplingFunction::Lanczos3,
+        )
+        .unwrap();
+        for (y, row) in resized.chunks_exact(destination_size.width).enumerate() {
+            let value = if y % 2 == 0 { 1000u16 } else { 50000 };
+            assert!(
+                row.iter().all(|&v| v.abs_diff(value) <= 1),
+                "{source_size:?} -> {destination_size:?}, row {y}: {row:?}"
+            );
+        }
+    }
+}
+
+/// Woven frame holds exactly the fields resized apart
+#[test]
+fn interlaced8_weaves_fields() {
+    for (source_size, destination_size) in sizes() {
+        let source = (0..source_size.width * source_size.height)
+            .map(|i| (i * 13 % 256) as u8)
+            .collect::<Vec<_>>();
+        let frame = resize_plane8_interlaced(
+            &source,
+            source_size,
+            destination_size,
+            ResamplingFunction::Lanczos3,
+        )
+        .unwrap();
+        let (top, bottom) = resize_plane8_fields(
+            &source,
+            source_size,
+            destination_size,
+            ResamplingFunction::Lanczos3,
+        )
+        .unwrap();
+        assert_eq!(
+            frame.len(),
+            destination_size.width * destination_size.height
+        );
+        assert_eq!(field_rows(&frame, destination_size, 0), top);
+        assert_eq!(field_rows(&frame, destination_size, 1), bottom);
+    }
+}
+
+/// Frame of a single row has an empty bottom field
+#[test]
+fn single_row_frames_are_rejected() {
+    let source = vec![0u8; 16 * 4];
+    for (source_size, destination_size, height) in [
+        (ImageSize::new(16, 1), ImageSize::new(8, 4), 1),
+        (ImageSize::new(16, 4), ImageSize::new(8, 1), 1),
+    ] {
+        assert_eq!(
+            resize_plane8_interlaced(
+                &source,
+                source_size,
+                destination_size,
+                ResamplingFunction::Bilinear,
+            ),
+            Err(PicScaleError::InterlacedTooShort(height))
+        );
+        assert_eq!(
+            resize_plane16_fields(
+                &[0u16; 16 * 4],
+                source_size,
+                destination_size,
+                10,
+                ResamplingFunction::Bilinear,
+            ),
+            Err(PicScaleError::InterlacedTooShort(height))
+        );
+    }
+    assert_eq!(
+        resize_plane8_interlaced(
+            &source,
+            ImageSize::new(16, 4),
+            ImageSize::new(8, 0),
+            ResamplingFunction::Bilinear,
+        ),
+        Err(PicScaleError::ZeroImageDimensions)
+    );
+}
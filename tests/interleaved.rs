@@ -0,0 +1,327 @@
+use pic_scale_safe::{
+    resize_interleaved16, resize_interleaved8, resize_interleaved_f32, resize_plane16,
+    resize_plane8, resize_plane_f32, ChannelLayout, EdgeMode, ImageSize, PassOrder, PicScaleError,
+    PremultiplyMode, ResamplingFunction, ResizeOptions, ResizePlan, StreamingResizer,
+    ThreadingPolicy,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 2] {
+    [
+        (ImageSize::new(43, 31), ImageSize::new(19, 23)),
+        (ImageSize::new(17, 12), ImageSize::new(40, 29)),
+    ]
+}
+
+fn deinterleave<T: Copy>(image: &[T], channels: usize, channel: usize) -> Vec<T> {
+    image
+        .iter()
+        .skip(channel)
+        .step_by(channels)
+        .copied()
+        .collect()
+}
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 113 % 256) as u8).collect()
+}
+
+/// Every channel of interleaved image is resampled exactly as the same channel
+/// resized as a separate plane
+fn assert_matches_planes<T: Copy + PartialEq + std::fmt::Debug, const CHANNELS: usize>(
+    source: &[T],
+    source_size: ImageSize,
+    destination_size: ImageSize,
+    interleaved: impl Fn(&[T]) -> Vec<T>,
+    plane: impl Fn(&[T]) -> Vec<T>,
+) {
+    let resized = interleaved(source);
+    assert_eq!(
+        resized.len(),
+        destination_size.width * destination_size.height * CHANNELS
+    );
+    for channel in 0..CHANNELS {
+        assert_eq!(
+            deinterleave(&resized, CHANNELS, channel),
+            plane(&deinterleave(source, CHANNELS, channel)),
+            "channel {channel}, {source_size:?} -> {destination_size:?}"
+        );
+    }
+}
+
+#[test]
+fn interleaved8_matches_planes() {
+    for (source_size, destination_size) in sizes() {
+        let source = pattern(source_size.width * source_size.height * 5);
+        assert_matches_planes::<u8, 5>(
+            &source,
+            source_size,
+            destination_size,
+            |s| {
+                resize_interleaved8::<5>(
+                    s,
+                    source_size,
+                    destination_size,
+                    ResamplingFunction::Lanczos3,
+                )
+                .unwrap()
+            },
+            |s| {
+                resize_plane8(
+                    s,
+                    source_size,
+                    destination_size,
+                    ResamplingFunction::Lanczos3,
+                )
+                .unwrap()
+            },
+        );
+    }
+}
+
+#[test]
+fn interleaved16_matches_planes() {
+    for bit_depth in [10, 16] {
+        for (source_size, destination_size) in sizes() {
+            let source = pattern(source_size.width * source_size.height * 7)
+                .iter()
+                .map(|&v| (v as u16) << (bit_depth - 8))
+                .collect::<Vec<_>>();
+            assert_matches_planes::<u16, 7>(
+                &source,
+                source_size,
+                destination_size,
+                |s| {
+                    resize_interleaved16::<7>(
+                        s,
+                        source_size,
+                        destination_size,
+                        bit_depth,
+                        ResamplingFunction::CatmullRom,
+                    )
+                    .unwrap()
+                },
+                |s| {
+                    resize_plane16(
+                        s,
+                        source_size,
+                        destination_size,
+                        bit_depth,
+                        ResamplingFunction::CatmullRom,
+                    )
+                    .unwrap()
+                },
+            );
+        }
+    }
+}
+
+#[test]
+fn interleaved_f32_matches_planes() {
+    for (source_size, destination_size) in sizes() {
+        let source = pattern(source_size.width * source_size.height * 6)
+            .iter()
+            .map(|&v| v as f32 / 255.)
+            .collect::<Vec<_>>();
+        assert_matches_planes::<f32, 6>(
+            &source,
+            source_size,
+            destination_size,
+            |s| {
+                resize_interleaved_f32::<6>(
+                    s,
+                    source_size,
+                    destination_size,
+                    ResamplingFunction::Bilinear,
+                )
+                .unwrap()
+            },
+            |s| {
+                resize_plane_f32(
+                    s,
+                    source_size,
+                    destination_size,
+                    ResamplingFunction::Bilinear,
+                )
+                .unwrap()
+            },
+        );
+    }
+}
+
+/// Same as [assert_matches_planes] for channels count known at runtime
+fn assert_channels_match_planes<T: Copy + PartialEq + std::fmt::Debug>(
+    source: &[T],
+    channels: usize,
+    destination_size: ImageSize,
+    interleaved: impl Fn(&[T]) -> Vec<T>,
+    plane: impl Fn(&[T]) -> Vec<T>,
+    context: &str,
+) {
+    let resized = interleaved(source);
+    assert_eq!(
+        resized.len(),
+        destination_size.width * destination_size.height * channels
+    );
+    for channel in 0..channels {
+        assert_eq!(
+            deinterleave(&resized, channels, channel),
+            plane(&deinterleave(source, channels, channel)),
+            "channel {channel} of {channels}, {context}"
+        );
+    }
+}
+
+fn plan_options() -> Vec<ResizeOptions> {
+    vec![
+        ResizeOptions::new(ResamplingFunction::Lanczos3)
+            .with_edge_mode(EdgeMode::Reflect)
+            .with_anti_ringing(0.8),
+        // Alpha isn't a thing for interleaved layout, nothing is premultiplied
+        ResizeOptions::new(ResamplingFunction::Bicubic).with_premultiply(PremultiplyMode::Auto),
+        ResizeOptions::new(ResamplingFunction::Lanczos3Jinc).with_ewa(true),
+        ResizeOptions::new(ResamplingFunction::Nearest),
+        ResizeOptions::new(ResamplingFunction::Area),
+    ]
+}
+
+/// Plan with interleaved layout resamples every channel as a separate plane,
+/// whatever the options and threading are
+#[test]
+fn interleaved_plan_matches_planes() {
+    let source_size = ImageSize::new(34, 26);
+    let destination_size = ImageSize::new(17, 13);
+    for channels in [5, 8] {
+        let layout = ChannelLayout::Interleaved(channels);
+        let source = pattern(source_size.width * source_size.height * channels);
+        for options in plan_options() {
+            let plane = ResizePlan::<u8>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Plane,
+                options,
+            )
+            .unwrap();
+            for threading in [ThreadingPolicy::Single, ThreadingPolicy::Threads(2)] {
+                let plan =
+                    ResizePlan::<u8>::with_options(source_size, destination_size, layout, options)
+                        .and_then(|plan| plan.with_threading(threading.clone()))
+                        .unwrap()
+                        .with_min_parallel_work(1);
+                assert_channels_match_planes(
+                    &source,
+                    channels,
+                    destination_size,
+                    |s| {
+                        let mut resized =
+                            vec![0u8; destination_size.width * destination_size.height * channels];
+                        plan.resize(s, &mut resized).unwrap();
+                        resized
+                    },
+                    |s| {
+                        let mut resized =
+                            vec![0u8; destination_size.width * destination_size.height];
+                        plane.resize(s, &mut resized).unwrap();
+                        resized
+                    },
+                    &format!("{options:?}, {threading:?}"),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn interleaved16_plan_matches_planes() {
+    let source_size = ImageSize::new(29, 17);
+    let destination_size = ImageSize::new(41, 11);
+    for bit_depth in [10, 16] {
+        let source = pattern(source_size.width * source_size.height * 6)
+            .iter()
+            .map(|&v| (v as u16) << (bit_depth - 8))
+            .collect::<Vec<_>>();
+        let options = ResizeOptions::new(ResamplingFunction::CatmullRom);
+        let plan = ResizePlan::<u16>::with_options(
+            source_size,
+            destination_size,
+            ChannelLayout::Interleaved(6),
+            bit_depth,
+            options,
+        )
+        .unwrap();
+        let plane = ResizePlan::<u16>::with_options(
+            source_size,
+            destination_size,
+            ChannelLayout::Plane,
+            bit_depth,
+            options,
+        )
+        .unwrap();
+        assert_channels_match_planes(
+            &source,
+            6,
+            destination_size,
+            |s| {
+                let mut resized = vec![0u16; destination_size.width * destination_size.height * 6];
+                plan.resize(s, &mut resized).unwrap();
+                resized
+            },
+            |s| {
+                let mut resized = vec![0u16; destination_size.width * destination_size.height];
+                plane.resize(s, &mut resized).unwrap();
+                resized
+            },
+            &format!("bit depth {bit_depth}"),
+        );
+    }
+}
+
+/// Rows pushed one by one give the same image as the whole image resized at once
+#[test]
+fn interleaved_plan_streams_rows() {
+    let source_size = ImageSize::new(23, 19);
+    let destination_size = ImageSize::new(31, 8);
+    let channels = 7;
+    let source = pattern(source_size.width * source_size.height * channels)
+        .iter()
+        .map(|&v| v as f32 / 255.)
+        .collect::<Vec<_>>();
+    // Rows are streamed through horizontal pass first
+    let plan = ResizePlan::<f32>::with_options(
+        source_size,
+        destination_size,
+        ChannelLayout::Interleaved(channels),
+        ResizeOptions::new(ResamplingFunction::Lanczos3)
+            .with_pass_order(PassOrder::HorizontalFirst),
+    )
+    .unwrap();
+    let row_length = destination_size.width * channels;
+    let mut expected = vec![0f32; row_length * destination_size.height];
+    plan.resize(&source, &mut expected).unwrap();
+
+    let mut streamed = vec![0f32; expected.len()];
+    let mut resizer = StreamingResizer::<f32>::new(plan).unwrap();
+    for row in source.chunks_exact(source_size.width * channels) {
+        resizer
+            .push_row(row, |y, row| {
+                streamed[y * row_length..(y + 1) * row_length].copy_from_slice(row)
+            })
+            .unwrap();
+    }
+    assert_eq!(streamed, expected);
+}
+
+#[test]
+fn interleaved_plan_channels_are_bounded() {
+    for channels in [0, ChannelLayout::MAX_CHANNELS + 1] {
+        assert_eq!(
+            ResizePlan::<u8>::new(
+                ImageSize::new(4, 4),
+                ImageSize::new(2, 2),
+                ChannelLayout::Interleaved(channels),
+                ResamplingFunction::Bilinear,
+            )
+            .err(),
+            Some(PicScaleError::UnsupportedChannelsCount(channels))
+        );
+    }
+}
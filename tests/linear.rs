@@ -0,0 +1,155 @@
+use pic_scale_safe::{
+    resize_plane8_with_alpha_linear, resize_rgb8_linear, resize_rgba8_linear, srgb_from_linear,
+    srgb_to_linear, ChannelLayout, ImageSize, ResamplingFunction, ResizePlan, TransferFunction,
+};
+
+const SOURCE: ImageSize = ImageSize {
+    width: 37,
+    height: 29,
+};
+
+const DESTINATION: ImageSize = ImageSize {
+    width: 15,
+    height: 44,
+};
+
+fn pattern(channels: usize) -> Vec<u8> {
+    (0..SOURCE.width * SOURCE.height * channels)
+        .map(|i| {
+            if channels == 4 && i % 4 == 3 {
+                (i * 29 % 256) as u8
+            } else {
+                (i * 71 % 256) as u8
+            }
+        })
+        .collect()
+}
+
+/// Resizes `f32` image with the same filter as 8-bit one
+fn resize_f32(source: &[f32], layout: ChannelLayout) -> Vec<f32> {
+    let plan =
+        ResizePlan::<f32>::new(SOURCE, DESTINATION, layout, ResamplingFunction::Bilinear).unwrap();
+    let mut destination = vec![0f32; DESTINATION.width * DESTINATION.height * layout.channels()];
+    plan.resize(source, &mut destination).unwrap();
+    destination
+}
+
+#[test]
+fn linear_light_matches_f32_reference() {
+    let source = pattern(3);
+    let linear = source
+        .iter()
+        .map(|&v| srgb_to_linear(v as f32 / 255.))
+        .collect::<Vec<_>>();
+    let expected = resize_f32(&linear, ChannelLayout::Rgb);
+    let resized = resize_rgb8_linear(
+        &source,
+        SOURCE,
+        DESTINATION,
+        ResamplingFunction::Bilinear,
+        TransferFunction::Srgb,
+    )
+    .unwrap();
+    for (i, (&resized, &expected)) in resized.iter().zip(expected.iter()).enumerate() {
+        let expected = (srgb_from_linear(expected.max(0.)) * 255.).round() as u8;
+        assert!(
+            resized.abs_diff(expected) <= 2,
+            "{i}: {resized} != {expected}"
+        );
+    }
+}
+
+/// Color is weighted by alpha in linear light, not with gamma encoded values
+#[test]
+fn alpha_is_associated_in_linear_light() {
+    let source = pattern(4);
+    let mut premultiplied = source
+        .chunks_exact(4)
+        .flat_map(|px| {
+            let alpha = px[3] as f32 / 255.;
+            let color = |v: u8| srgb_to_linear(v as f32 / 255.) * alpha;
+            [color(px[0]), color(px[1]), color(px[2]), alpha]
+        })
+        .collect::<Vec<_>>();
+    premultiplied = resize_f32(&premultiplied, ChannelLayout::Rgba);
+    let resized = resize_rgba8_linear(
+        &source,
+        SOURCE,
+        DESTINATION,
+        ResamplingFunction::Bilinear,
+        TransferFunction::Srgb,
+    )
+    .unwrap();
+    for (i, (resized, expected)) in resized
+        .chunks_exact(4)
+        .zip(premultiplied.chunks_exact(4))
+        .enumerate()
+    {
+        let alpha = expected[3];
+        assert!(
+            resized[3].abs_diff((alpha * 255.).round() as u8) <= 1,
+            "{i}: {resized:?} {expected:?}"
+        );
+        // Colors of nearly transparent pixels are not representable precisely
+        if alpha < 0.25 {
+            continue;
+        }
+        for c in 0..3 {
+            let color = (expected[c] / alpha).clamp(0., 1.);
+            let color = (srgb_from_linear(color) * 255.).round() as u8;
+            assert!(
+                resized[c].abs_diff(color) <= 3,
+                "{i}: {resized:?} != {color} at {c}"
+            );
+        }
+    }
+}
+
+/// Color of fully transparent pixels doesn't bleed into visible ones
+#[test]
+fn transparent_color_does_not_bleed() {
+    let source = (0..SOURCE.width * SOURCE.height)
+        .flat_map(|i| if i % 2 == 0 { [255, 0] } else { [40, 255] })
+        .collect::<Vec<u8>>();
+    let resized = resize_plane8_with_alpha_linear(
+        &source,
+        SOURCE,
+        DESTINATION,
+        ResamplingFunction::Lanczos3,
+        TransferFunction::Srgb,
+    )
+    .unwrap();
+    for pixel in resized.chunks_exact(2) {
+        assert!(pixel[0].abs_diff(40) <= 1, "{pixel:?}");
+    }
+}
+
+/// Opaque image is resized the same way as one without alpha
+#[test]
+fn opaque_alpha_keeps_colors() {
+    let source = pattern(3);
+    let with_alpha = source
+        .chunks_exact(3)
+        .flat_map(|px| [px[0], px[1], px[2], 255])
+        .collect::<Vec<_>>();
+    let expected = resize_rgb8_linear(
+        &source,
+        SOURCE,
+        DESTINATION,
+        ResamplingFunction::Bilinear,
+        TransferFunction::Srgb,
+    )
+    .unwrap();
+    let resized = resize_rgba8_linear(
+        &with_alpha,
+        SOURCE,
+        DESTINATION,
+        ResamplingFunction::Bilinear,
+        TransferFunction::Srgb,
+    )
+    .unwrap();
+    for (resized, expected) in resized.chunks_exact(4).zip(expected.chunks_exact(3)) {
+        assert_eq!(&resized[..3], expected);
+        assert_eq!(resized[3], 255);
+    }
+}
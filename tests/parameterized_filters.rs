@@ -0,0 +1,136 @@
+use pic_scale_safe::{resize_plane_f32, ImageSize, PicScaleError, ResamplingFunction};
+
+fn resize_with(function: ResamplingFunction) -> Result<Vec<f32>, PicScaleError> {
+    let source = (0..32 * 32)
+        .map(|i| (i % 7) as f32 / 7.)
+        .collect::<Vec<_>>();
+    resize_plane_f32(
+        &source,
+        ImageSize::new(32, 32),
+        ImageSize::new(13, 11),
+        function,
+    )
+}
+
+#[test]
+fn kaiser_with_large_beta_is_rejected() {
+    for beta in [50.5, 100., 1000., f32::INFINITY, f32::NAN, -1.] {
+        assert_eq!(
+            resize_with(ResamplingFunction::KaiserBeta { beta: beta.into() }),
+            Err(PicScaleError::InvalidResamplingFunction),
+            "beta {beta}"
+        );
+    }
+}
+
+#[test]
+fn kaiser_with_largest_beta_terminates() {
+    let resized = resize_with(ResamplingFunction::KaiserBeta { beta: 50f32.into() }).unwrap();
+    assert!(resized.iter().all(|v| v.is_finite()));
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (&a, &e)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!((a - e).abs() < 1e-5, "sample {i}: {a} != {e}");
+    }
+}
+
+#[test]
+fn lanczos_lobes_match_fixed_variants() {
+    for (lobes, fixed) in [
+        (2, ResamplingFunction::Lanczos2),
+        (3, ResamplingFunction::Lanczos3),
+        (4, ResamplingFunction::Lanczos4),
+        (6, ResamplingFunction::Lanczos6),
+    ] {
+        assert_close(
+            &resize_with(ResamplingFunction::Lanczos { lobes }).unwrap(),
+            &resize_with(fixed).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn lobes_are_bounded() {
+    for lobes in [0, 65, 1000, u32::MAX] {
+        for function in [
+            ResamplingFunction::Lanczos { lobes },
+            ResamplingFunction::BlackmanSinc { lobes },
+        ] {
+            assert_eq!(
+                resize_with(function),
+                Err(PicScaleError::InvalidResamplingFunction),
+                "{function:?}"
+            );
+        }
+    }
+    for function in [
+        ResamplingFunction::Lanczos { lobes: 64 },
+        ResamplingFunction::BlackmanSinc { lobes: 64 },
+    ] {
+        let resized = resize_with(function).unwrap();
+        assert!(resized.iter().all(|v| v.is_finite()), "{function:?}");
+    }
+}
+
+/// Flat image stays flat, weights are normalized whatever the amount of lobes is
+#[test]
+fn blackman_sinc_keeps_flat_image() {
+    for lobes in [1, 2, 3, 8] {
+        let resized = resize_plane_f32(
+            &[0.25; 32 * 32],
+            ImageSize::new(32, 32),
+            ImageSize::new(13, 45),
+            ResamplingFunction::BlackmanSinc { lobes },
+        )
+        .unwrap();
+        assert!(
+            resized.iter().all(|v| (v - 0.25).abs() < 1e-5),
+            "lobes {lobes}"
+        );
+    }
+}
+
+#[test]
+fn gaussian_sigma_is_bounded() {
+    for sigma in [0., -1., 21.5, 1000., f32::INFINITY, f32::NAN] {
+        assert_eq!(
+            resize_with(ResamplingFunction::GaussianSigma {
+                sigma: sigma.into()
+            }),
+            Err(PicScaleError::InvalidResamplingFunction),
+            "sigma {sigma}"
+        );
+    }
+    let resized = resize_with(ResamplingFunction::GaussianSigma {
+        sigma: 21f32.into(),
+    })
+    .unwrap();
+    assert!(resized.iter().all(|v| v.is_finite()));
+}
+
+/// Wider gaussian is smoothing stronger, so alternating rows fade more
+#[test]
+fn gaussian_sigma_controls_smoothing() {
+    let source = (0..32 * 32)
+        .map(|i| ((i / 32) % 2) as f32)
+        .collect::<Vec<_>>();
+    let contrast = |sigma: f32| {
+        let resized = resize_plane_f32(
+            &source,
+            ImageSize::new(32, 32),
+            ImageSize::new(32, 48),
+            ResamplingFunction::GaussianSigma {
+                sigma: sigma.into(),
+            },
+        )
+        .unwrap();
+        let max = resized.iter().copied().fold(f32::MIN, f32::max);
+        let min = resized.iter().copied().fold(f32::MAX, f32::min);
+        max - min
+    };
+    let (sharp, soft) = (contrast(0.3), contrast(1.5));
+    assert!(sharp > soft, "{sharp} <= {soft}");
+    assert!(sharp > 0.5, "{sharp}");
+}
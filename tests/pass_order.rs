@@ -0,0 +1,44 @@
+use pic_scale_safe::{
+    ChannelLayout, ImageSize, PassOrder, ResamplingFunction, ResizeOptions, ResizePlan,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 3] {
+    [
+        (ImageSize::new(90, 20), ImageSize::new(30, 70)),
+        (ImageSize::new(17, 61), ImageSize::new(50, 14)),
+        (ImageSize::new(40, 40), ImageSize::new(23, 23)),
+    ]
+}
+
+fn resize(source_size: ImageSize, destination_size: ImageSize, order: PassOrder) -> Vec<f32> {
+    let source = (0..source_size.width * source_size.height * 3)
+        .map(|i| (i * 37 % 101) as f32 / 100.)
+        .collect::<Vec<_>>();
+    let plan = ResizePlan::<f32>::with_options(
+        source_size,
+        destination_size,
+        ChannelLayout::Rgb,
+        ResizeOptions::new(ResamplingFunction::Lanczos3).with_pass_order(order),
+    )
+    .unwrap();
+    let mut destination = vec![0f32; destination_size.width * destination_size.height * 3];
+    plan.resize(&source, &mut destination).unwrap();
+    destination
+}
+
+/// Separable passes commute, so any order gives the same image up to rounding
+#[test]
+fn pass_orders_are_equivalent() {
+    for (source_size, destination_size) in sizes() {
+        let vertical = resize(source_size, destination_size, PassOrder::VerticalFirst);
+        let horizontal = resize(source_size, destination_size, PassOrder::HorizontalFirst);
+        for (v, h) in vertical.iter().zip(horizontal.iter()) {
+            assert!(
+                (v - h).abs() < 1e-4,
+                "{source_size:?} -> {destination_size:?}: {v} != {h}"
+            );
+        }
+        let auto = resize(source_size, destination_size, PassOrder::Auto);
+        assert!(auto == vertical || auto == horizontal);
+    }
+}
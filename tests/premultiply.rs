@@ -0,0 +1,151 @@
+use pic_scale_safe::{
+    premultiply_la8, premultiply_rgba8, premultiply_rgba_f32, unpremultiply_la8,
+    unpremultiply_rgba8, unpremultiply_rgba_f32, ChannelLayout, ImageSize, PremultiplyMode,
+    ResamplingFunction, ResizeOptions, ResizePlan,
+};
+
+const SOURCE: ImageSize = ImageSize {
+    width: 37,
+    height: 29,
+};
+
+const DESTINATION: ImageSize = ImageSize {
+    width: 15,
+    height: 44,
+};
+
+/// Destinations scaled along one axis at a time
+const SINGLE_AXIS_DESTINATIONS: [ImageSize; 2] = [
+    ImageSize {
+        width: 15,
+        height: 29,
+    },
+    ImageSize {
+        width: 37,
+        height: 44,
+    },
+];
+
+fn pattern(channels: usize) -> Vec<u8> {
+    (0..SOURCE.width * SOURCE.height * channels)
+        .map(|i| {
+            if i % channels == channels - 1 {
+                (i * 29 % 256) as u8
+            } else {
+                (i * 71 % 256) as u8
+            }
+        })
+        .collect()
+}
+
+fn resize8(
+    source: &[u8],
+    destination_size: ImageSize,
+    layout: ChannelLayout,
+    premultiply: PremultiplyMode,
+) -> Vec<u8> {
+    let options = ResizeOptions::new(ResamplingFunction::Bilinear).with_premultiply(premultiply);
+    let plan = ResizePlan::<u8>::with_options(SOURCE, destination_size, layout, options).unwrap();
+    let mut destination =
+        vec![0u8; destination_size.width * destination_size.height * layout.channels()];
+    plan.resize(source, &mut destination).unwrap();
+    destination
+}
+
+/// Associating alpha on the fly is the same as associating it before and after resizing
+#[test]
+fn auto_premultiply_matches_manual8() {
+    for destination_size in SINGLE_AXIS_DESTINATIONS {
+        let source = pattern(4);
+        let mut premultiplied = source.clone();
+        premultiply_rgba8(&mut premultiplied);
+        let mut expected = resize8(
+            &premultiplied,
+            destination_size,
+            ChannelLayout::Rgba,
+            PremultiplyMode::Disabled,
+        );
+        unpremultiply_rgba8(&mut expected);
+        assert_eq!(
+            resize8(
+                &source,
+                destination_size,
+                ChannelLayout::Rgba,
+                PremultiplyMode::Auto
+            ),
+            expected
+        );
+
+        let source = pattern(2);
+        let mut premultiplied = source.clone();
+        premultiply_la8(&mut premultiplied);
+        let mut expected = resize8(
+            &premultiplied,
+            destination_size,
+            ChannelLayout::PlaneWithAlpha,
+            PremultiplyMode::Disabled,
+        );
+        unpremultiply_la8(&mut expected);
+        assert_eq!(
+            resize8(
+                &source,
+                destination_size,
+                ChannelLayout::PlaneWithAlpha,
+                PremultiplyMode::Auto
+            ),
+            expected
+        );
+    }
+}
+
+#[test]
+fn auto_premultiply_matches_manual_f32() {
+    let source = pattern(4)
+        .iter()
+        .map(|&v| v as f32 / 255.)
+        .collect::<Vec<_>>();
+    for destination_size in SINGLE_AXIS_DESTINATIONS {
+        let resize = |source: &[f32], premultiply: PremultiplyMode| {
+            let options =
+                ResizeOptions::new(ResamplingFunction::Lanczos3).with_premultiply(premultiply);
+            let plan = ResizePlan::<f32>::with_options(
+                SOURCE,
+                destination_size,
+                ChannelLayout::Rgba,
+                options,
+            )
+            .unwrap();
+            let mut destination = vec![0f32; destination_size.width * destination_size.height * 4];
+            plan.resize(source, &mut destination).unwrap();
+            destination
+        };
+        let mut premultiplied = source.clone();
+        premultiply_rgba_f32(&mut premultiplied);
+        let mut expected = resize(&premultiplied, PremultiplyMode::Disabled);
+        unpremultiply_rgba_f32(&mut expected);
+        assert_eq!(resize(&source, PremultiplyMode::Auto), expected);
+    }
+}
+
+/// Color of fully transparent pixels doesn't bleed into visible ones
+#[test]
+fn transparent_color_does_not_bleed() {
+    let source = (0..SOURCE.width * SOURCE.height)
+        .flat_map(|i| {
+            if i % 2 == 0 {
+                [255, 0, 0, 0]
+            } else {
+                [0, 255, 0, 255]
+            }
+        })
+        .collect::<Vec<u8>>();
+    let resized = resize8(
+        &source,
+        DESTINATION,
+        ChannelLayout::Rgba,
+        PremultiplyMode::Auto,
+    );
+    for pixel in resized.chunks_exact(4) {
+        assert_eq!(pixel[0], 0, "{pixel:?}");
+    }
+}
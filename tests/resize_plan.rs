@@ -0,0 +1,93 @@
+use pic_scale_safe::{
+    resize_plane_f32, resize_rgb16, resize_rgba8, ChannelLayout, ImageSize, ResamplingFunction,
+    ResizePlan,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 2] {
+    [
+        (ImageSize::new(53, 37), ImageSize::new(21, 60)),
+        (ImageSize::new(24, 31), ImageSize::new(48, 9)),
+    ]
+}
+
+fn pattern(size: ImageSize, channels: usize, seed: usize) -> Vec<u16> {
+    (0..size.width * size.height * channels)
+        .map(|i| ((i * 61 + seed * 17) % 1024) as u16)
+        .collect()
+}
+
+/// Same plan executed over different images gives the same result as resizing each of them anew
+#[test]
+fn reused_plan_matches_free_functions() {
+    for (source_size, destination_size) in sizes() {
+        let plan8 = ResizePlan::<u8>::new(
+            source_size,
+            destination_size,
+            ChannelLayout::Rgba,
+            ResamplingFunction::Lanczos3,
+        )
+        .unwrap();
+        let plan16 = ResizePlan::<u16>::new(
+            source_size,
+            destination_size,
+            ChannelLayout::Rgb,
+            10,
+            ResamplingFunction::CatmullRom,
+        )
+        .unwrap();
+        let plan_f32 = ResizePlan::<f32>::new(
+            source_size,
+            destination_size,
+            ChannelLayout::Plane,
+            ResamplingFunction::Bicubic,
+        )
+        .unwrap();
+        let mut destination8 = vec![0u8; destination_size.width * destination_size.height * 4];
+        let mut destination16 = vec![0u16; destination_size.width * destination_size.height * 3];
+        let mut destination_f32 = vec![0f32; destination_size.width * destination_size.height];
+        let mut scratch8 = Vec::new();
+        for seed in 0..3 {
+            let source8 = pattern(source_size, 4, seed)
+                .iter()
+                .map(|&v| v as u8)
+                .collect::<Vec<_>>();
+            plan8
+                .resize_with_scratch(&source8, &mut destination8, &mut scratch8)
+                .unwrap();
+            let expected8 = resize_rgba8(
+                &source8,
+                source_size,
+                destination_size,
+                ResamplingFunction::Lanczos3,
+            )
+            .unwrap();
+            assert_eq!(destination8, expected8);
+
+            let source16 = pattern(source_size, 3, seed);
+            plan16.resize(&source16, &mut destination16).unwrap();
+            let expected16 = resize_rgb16(
+                &source16,
+                source_size,
+                destination_size,
+                10,
+                ResamplingFunction::CatmullRom,
+            )
+            .unwrap();
+            assert_eq!(destination16, expected16);
+
+            let source_f32 = pattern(source_size, 1, seed)
+                .iter()
+                .map(|&v| v as f32 / 1023.)
+                .collect::<Vec<_>>();
+            plan_f32.resize(&source_f32, &mut destination_f32).unwrap();
+            let expected_f32 = resize_plane_f32(
+                &source_f32,
+                source_size,
+                destination_size,
+                ResamplingFunction::Bicubic,
+            )
+            .unwrap();
+            assert_eq!(destination_f32, expected_f32);
+        }
+    }
+}
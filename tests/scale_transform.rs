@@ -0,0 +1,39 @@
+use pic_scale_safe::{
+    ChannelLayout, ImageSize, PicScaleError, ResamplingFunction, ResizeOptions, ResizePlan,
+    ScaleTransform,
+};
+
+fn plan(scale: f32) -> Result<ResizePlan<f32>, PicScaleError> {
+    ResizePlan::<f32>::with_options(
+        ImageSize::new(64, 64),
+        ImageSize::new(64, 64),
+        ChannelLayout::Plane,
+        ResizeOptions::new(ResamplingFunction::Lanczos3)
+            .with_scale_transform(ScaleTransform::new(scale, 1., 0., 0.)),
+    )
+}
+
+/// Minification is bounded, kernel would grow with it up to exhausting memory
+#[test]
+fn scale_below_minimum_is_rejected() {
+    assert!(plan(ScaleTransform::MIN_SCALE).is_ok());
+    for scale in [ScaleTransform::MIN_SCALE / 2., 1e-7, 0., -1.] {
+        assert_eq!(
+            plan(scale).err(),
+            Some(PicScaleError::InvalidScaleTransform),
+            "{scale}"
+        );
+    }
+}
+
+#[test]
+fn too_large_kernel_is_rejected() {
+    // Lanczos3 spans 6 source pixels per destination one, so 400000 are stretched into a single
+    let plan = ResizePlan::<u8>::new(
+        ImageSize::new(400_000, 1),
+        ImageSize::new(1, 1),
+        ChannelLayout::Plane,
+        ResamplingFunction::Lanczos3,
+    );
+    assert_eq!(plan.err(), Some(PicScaleError::KernelTooLarge(2_400_000)));
+}
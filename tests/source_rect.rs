@@ -0,0 +1,86 @@
+use pic_scale_safe::{
+    ChannelLayout, ImageSize, ResamplingFunction, ResizeOptions, ResizePlan, SourceRect,
+};
+
+const SOURCE: ImageSize = ImageSize {
+    width: 48,
+    height: 40,
+};
+
+fn pattern(size: ImageSize) -> Vec<f32> {
+    (0..size.width * size.height)
+        .map(|i| (i * 37 % 101) as f32 / 100.)
+        .collect()
+}
+
+fn crop(source: &[f32], size: ImageSize, x: usize, y: usize, region: ImageSize) -> Vec<f32> {
+    source
+        .chunks_exact(size.width)
+        .skip(y)
+        .take(region.height)
+        .flat_map(|row| row[x..x + region.width].iter().copied())
+        .collect()
+}
+
+fn resize(
+    source: &[f32],
+    size: ImageSize,
+    destination_size: ImageSize,
+    options: ResizeOptions,
+) -> Vec<f32> {
+    let plan =
+        ResizePlan::<f32>::with_options(size, destination_size, ChannelLayout::Plane, options)
+            .unwrap();
+    let mut destination = vec![0f32; destination_size.width * destination_size.height];
+    plan.resize(source, &mut destination).unwrap();
+    destination
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!((a - e).abs() < 1e-5, "{i}: {a} != {e}");
+    }
+}
+
+/// Area kernel on 2x downscale never reaches outside of the rectangle,
+/// so it is the same as cropping the source first
+#[test]
+fn area_source_rect_matches_crop_then_resize() {
+    let source = pattern(SOURCE);
+    let region = ImageSize::new(20, 14);
+    let destination_size = ImageSize::new(10, 7);
+    let options = ResizeOptions::new(ResamplingFunction::Area);
+    let resized = resize(
+        &source,
+        SOURCE,
+        destination_size,
+        options.with_source_rect(SourceRect::new(6., 10., 20., 14.)),
+    );
+    let expected = resize(
+        &crop(&source, SOURCE, 6, 10, region),
+        region,
+        destination_size,
+        options,
+    );
+    assert_close(&resized, &expected);
+}
+
+/// Wider kernels sample around the rectangle, so it is the same as cropping larger resize
+#[test]
+fn source_rect_matches_resize_then_crop() {
+    let source = pattern(SOURCE);
+    let options = ResizeOptions::new(ResamplingFunction::Lanczos3);
+    let resized = resize(
+        &source,
+        SOURCE,
+        ImageSize::new(8, 6),
+        options.with_source_rect(SourceRect::new(16., 12., 16., 12.)),
+    );
+    let whole_size = ImageSize::new(24, 20);
+    let whole = resize(&source, SOURCE, whole_size, options);
+    assert_close(
+        &resized,
+        &crop(&whole, whole_size, 8, 6, ImageSize::new(8, 6)),
+    );
+}
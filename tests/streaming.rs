@@ -0,0 +1,131 @@
+use pic_scale_safe::{
+    ChannelLayout, EdgeMode, ImageSize, PassOrder, PicScaleError, PremultiplyMode,
+    ResamplingFunction, ResizeOptions, ResizePlan, StreamingResizer,
+};
+
+fn sizes() -> [(ImageSize, ImageSize); 3] {
+    [
+        (ImageSize::new(67, 45), ImageSize::new(31, 17)),
+        (ImageSize::new(29, 23), ImageSize::new(70, 61)),
+        (ImageSize::new(40, 12), ImageSize::new(17, 40)),
+    ]
+}
+
+fn options(resampling_function: ResamplingFunction) -> ResizeOptions {
+    ResizeOptions::new(resampling_function).with_pass_order(PassOrder::HorizontalFirst)
+}
+
+fn stream8(plan: ResizePlan<u8>, source: &[u8]) -> Vec<u8> {
+    let channels = plan.layout().channels();
+    let source_row = plan.source_size().width * channels;
+    let destination_row = plan.destination_size().width * channels;
+    let mut destination = vec![0u8; destination_row * plan.destination_size().height];
+    let mut resizer = StreamingResizer::<u8>::new(plan).unwrap();
+    for row in source.chunks_exact(source_row) {
+        resizer
+            .push_row(row, |y, row| {
+                destination[y * destination_row..(y + 1) * destination_row].copy_from_slice(row)
+            })
+            .unwrap();
+    }
+    assert!(resizer.is_finished());
+    destination
+}
+
+fn stream_f32(plan: ResizePlan<f32>, source: &[f32]) -> Vec<f32> {
+    let channels = plan.layout().channels();
+    let source_row = plan.source_size().width * channels;
+    let destination_row = plan.destination_size().width * channels;
+    let mut destination = vec![0f32; destination_row * plan.destination_size().height];
+    let mut resizer = StreamingResizer::<f32>::new(plan).unwrap();
+    for row in source.chunks_exact(source_row) {
+        resizer
+            .push_row(row, |y, row| {
+                destination[y * destination_row..(y + 1) * destination_row].copy_from_slice(row)
+            })
+            .unwrap();
+    }
+    assert!(resizer.is_finished());
+    destination
+}
+
+fn pattern8(size: ImageSize, channels: usize) -> Vec<u8> {
+    (0..size.width * size.height * channels)
+        .map(|i| (i * 73 % 256) as u8)
+        .collect()
+}
+
+#[test]
+fn streaming_u8_matches_plan() {
+    for (source_size, destination_size) in sizes() {
+        for function in [
+            ResamplingFunction::Lanczos3,
+            ResamplingFunction::Bilinear,
+            ResamplingFunction::MitchellNetravalli,
+        ] {
+            for options in [
+                options(function),
+                options(function).with_edge_mode(EdgeMode::Reflect),
+                options(function).with_premultiply(PremultiplyMode::Auto),
+            ] {
+                let source = pattern8(source_size, 4);
+                let plan = ResizePlan::<u8>::with_options(
+                    source_size,
+                    destination_size,
+                    ChannelLayout::Rgba,
+                    options,
+                )
+                .unwrap();
+                let mut expected = vec![0u8; destination_size.width * destination_size.height * 4];
+                plan.resize(&source, &mut expected).unwrap();
+                assert_eq!(
+                    stream8(plan, &source),
+                    expected,
+                    "{source_size:?} -> {destination_size:?}, {options:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_f32_matches_plan() {
+    for (source_size, destination_size) in sizes() {
+        let source = pattern8(source_size, 3)
+            .iter()
+            .map(|&v| v as f32 / 255.)
+            .collect::<Vec<_>>();
+        let plan = ResizePlan::<f32>::with_options(
+            source_size,
+            destination_size,
+            ChannelLayout::Rgb,
+            options(ResamplingFunction::Lanczos3),
+        )
+        .unwrap();
+        let mut expected = vec![0f32; destination_size.width * destination_size.height * 3];
+        plan.resize(&source, &mut expected).unwrap();
+        assert_eq!(stream_f32(plan, &source), expected);
+    }
+}
+
+#[test]
+fn rows_after_source_height_are_rejected() {
+    let source_size = ImageSize::new(8, 3);
+    let plan = ResizePlan::<u8>::new(
+        source_size,
+        ImageSize::new(4, 6),
+        ChannelLayout::Plane,
+        ResamplingFunction::Bilinear,
+    )
+    .unwrap();
+    let mut resizer = StreamingResizer::<u8>::new(plan).unwrap();
+    let row = [0u8; 8];
+    for _ in 0..source_size.height {
+        resizer.push_row(&row, |_, _| {}).unwrap();
+    }
+    assert!(resizer.is_finished());
+    assert_eq!(
+        resizer.push_row(&row, |_, _| {}),
+        Err(PicScaleError::TooManyRows(source_size.height))
+    );
+}
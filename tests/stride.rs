@@ -0,0 +1,202 @@
+use pic_scale_safe::{
+    ChannelLayout, EdgeMode, ImageSize, MismatchedSize, PassOrder, PicScaleError, PremultiplyMode,
+    ResamplingFunction, ResizeOptions, ResizePlan, ThreadingPolicy,
+};
+
+const PADDING: usize = 5;
+const FILL: u8 = 0xAB;
+
+fn cases() -> Vec<(ImageSize, ImageSize, ResizeOptions)> {
+    let options = ResizeOptions::new(ResamplingFunction::Lanczos3);
+    vec![
+        (ImageSize::new(31, 23), ImageSize::new(13, 40), options),
+        (ImageSize::new(31, 23), ImageSize::new(13, 23), options),
+        (ImageSize::new(31, 23), ImageSize::new(31, 40), options),
+        (ImageSize::new(31, 23), ImageSize::new(31, 23), options),
+        (
+            ImageSize::new(31, 23),
+            ImageSize::new(13, 40),
+            options.with_pass_order(PassOrder::VerticalFirst),
+        ),
+        (
+            ImageSize::new(31, 23),
+            ImageSize::new(13, 40),
+            options.with_edge_mode(EdgeMode::Reflect),
+        ),
+        (
+            ImageSize::new(31, 23),
+            ImageSize::new(13, 40),
+            options.with_premultiply(PremultiplyMode::Auto),
+        ),
+        (
+            ImageSize::new(31, 23),
+            ImageSize::new(13, 40),
+            ResizeOptions::new(ResamplingFunction::Lanczos3Jinc).with_ewa(true),
+        ),
+        (
+            ImageSize::new(31, 23),
+            ImageSize::new(62, 69),
+            ResizeOptions::new(ResamplingFunction::Nearest),
+        ),
+        (
+            ImageSize::new(62, 46),
+            ImageSize::new(31, 23),
+            ResizeOptions::new(ResamplingFunction::Area),
+        ),
+    ]
+}
+
+/// Copies tightly packed image into rows of `stride` elements, last row is left without padding
+fn with_stride<T: Copy>(image: &[T], row_length: usize, stride: usize, fill: T) -> Vec<T> {
+    let rows = image.len() / row_length;
+    let mut strided = vec![fill; stride * (rows - 1) + row_length];
+    for (dst, src) in strided
+        .chunks_mut(stride)
+        .zip(image.chunks_exact(row_length))
+    {
+        dst[..row_length].copy_from_slice(src);
+    }
+    strided
+}
+
+fn pattern(size: ImageSize) -> Vec<u8> {
+    (0..size.width * size.height * 4)
+        .map(|i| {
+            if i % 4 == 3 {
+                (128 + i * 7 % 128) as u8
+            } else {
+                (i * 71 % 256) as u8
+            }
+        })
+        .collect()
+}
+
+/// Padded rows hold the same pixels as tightly packed ones, and padding is never written
+#[test]
+fn padded_rows_match_packed_image() {
+    for threading in [ThreadingPolicy::Single, ThreadingPolicy::Threads(2)] {
+        for (source_size, destination_size, options) in cases() {
+            let plan = ResizePlan::<u8>::with_options(
+                source_size,
+                destination_size,
+                ChannelLayout::Rgba,
+                options,
+            )
+            .and_then(|plan| plan.with_threading(threading.clone()))
+            .unwrap()
+            .with_min_parallel_work(1);
+            let source = pattern(source_size);
+            let mut expected = vec![0u8; destination_size.width * destination_size.height * 4];
+            plan.resize(&source, &mut expected).unwrap();
+
+            let source_row_length = source_size.width * 4;
+            let destination_row_length = destination_size.width * 4;
+            let source_stride = source_row_length + PADDING * 4;
+            let destination_stride = destination_row_length + PADDING * 4;
+            let strided_source = with_stride(&source, source_row_length, source_stride, FILL);
+            let mut destination = with_stride(
+                &vec![FILL; expected.len()],
+                destination_row_length,
+                destination_stride,
+                FILL,
+            );
+            plan.resize_with_stride(
+                &strided_source,
+                source_stride,
+                &mut destination,
+                destination_stride,
+                &mut Vec::new(),
+            )
+            .unwrap();
+            assert_eq!(
+                destination,
+                with_stride(&expected, destination_row_length, destination_stride, FILL),
+                "{source_size:?} -> {destination_size:?}, {options:?}, {threading:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn padded_rows_match_packed_image_f32() {
+    let source_size = ImageSize::new(29, 17);
+    let destination_size = ImageSize::new(11, 30);
+    let plan = ResizePlan::<f32>::new(
+        source_size,
+        destination_size,
+        ChannelLayout::Rgb,
+        ResamplingFunction::Bicubic,
+    )
+    .unwrap();
+    let source = (0..source_size.width * source_size.height * 3)
+        .map(|i| (i * 37 % 101) as f32 / 100.)
+        .collect::<Vec<_>>();
+    let mut expected = vec![0f32; destination_size.width * destination_size.height * 3];
+    plan.resize(&source, &mut expected).unwrap();
+
+    let source_stride = source_size.width * 3 + 7;
+    let destination_stride = destination_size.width * 3 + 2;
+    let mut destination = vec![-1f32; destination_stride * destination_size.height];
+    plan.resize_with_stride(
+        &with_stride(&source, source_size.width * 3, source_stride, -1.),
+        source_stride,
+        &mut destination,
+        destination_stride,
+        &mut Vec::new(),
+    )
+    .unwrap();
+    let mut padded_expected = with_stride(
+        &expected,
+        destination_size.width * 3,
+        destination_stride,
+        -1.,
+    );
+    // Longer destination is accepted, elements past the last row stay as is
+    padded_expected.resize(destination.len(), -1.);
+    assert_eq!(destination, padded_expected);
+}
+
+#[test]
+fn short_buffers_are_rejected() {
+    let source_size = ImageSize::new(8, 6);
+    let destination_size = ImageSize::new(4, 3);
+    let plan = ResizePlan::<u8>::new(
+        source_size,
+        destination_size,
+        ChannelLayout::Rgb,
+        ResamplingFunction::Bilinear,
+    )
+    .unwrap();
+    let source_stride = 30;
+    let destination_stride = 16;
+    let source = vec![0u8; source_stride * 5 + 8 * 3 - 1];
+    let mut destination = vec![0u8; destination_stride * 2 + 4 * 3];
+    assert_eq!(
+        plan.resize_with_stride(
+            &source,
+            source_stride,
+            &mut destination,
+            destination_stride,
+            &mut Vec::new(),
+        ),
+        Err(PicScaleError::SourceSizeMismatch(MismatchedSize {
+            expected: source_stride * 5 + 8 * 3,
+            received: source.len(),
+        }))
+    );
+    let source = vec![0u8; source_stride * 5 + 8 * 3];
+    let mut destination = vec![0u8; destination_stride * 2 + 4 * 3 - 1];
+    assert_eq!(
+        plan.resize_with_stride(
+            &source,
+            source_stride,
+            &mut destination,
+            destination_stride,
+            &mut Vec::new(),
+        ),
+        Err(PicScaleError::DestinationSizeMismatch(MismatchedSize {
+            expected: destination_stride * 2 + 4 * 3,
+            received: destination.len(),
+        }))
+    );
+}
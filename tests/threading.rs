@@ -0,0 +1,90 @@
+use pic_scale_safe::{
+    image16_to_linear16, image16_to_linear16_with_threading, resize_rgba16, resize_rgba8,
+    ChannelLayout, ImageSize, PicScaleError, ResamplingFunction, ResizePlan, ThreadingPolicy,
+    TransferFunction,
+};
+
+fn pattern<T: From<u8>>(size: ImageSize, channels: usize) -> Vec<T> {
+    (0..size.width * size.height * channels)
+        .map(|i| T::from((i * 31 % 251) as u8))
+        .collect()
+}
+
+#[test]
+fn resize_with_threading_matches_global_pool() {
+    let source_size = ImageSize::new(301, 257);
+    let destination_size = ImageSize::new(149, 383);
+    let source8 = pattern::<u8>(source_size, 4);
+    let source16 = pattern::<u16>(source_size, 4);
+    let expected8 = resize_rgba8(
+        &source8,
+        source_size,
+        destination_size,
+        ResamplingFunction::Lanczos3,
+    )
+    .unwrap();
+    let expected16 = resize_rgba16(
+        &source16,
+        source_size,
+        destination_size,
+        10,
+        ResamplingFunction::Lanczos3,
+    )
+    .unwrap();
+    for threading in [ThreadingPolicy::Single, ThreadingPolicy::Threads(3)] {
+        let plan8 = ResizePlan::<u8>::new(
+            source_size,
+            destination_size,
+            ChannelLayout::Rgba,
+            ResamplingFunction::Lanczos3,
+        )
+        .and_then(|plan| plan.with_threading(threading.clone()))
+        .unwrap();
+        let mut resized8 = vec![0u8; expected8.len()];
+        plan8.resize(&source8, &mut resized8).unwrap();
+        assert_eq!(resized8, expected8, "{threading:?}");
+        let plan16 = ResizePlan::<u16>::new(
+            source_size,
+            destination_size,
+            ChannelLayout::Rgba,
+            10,
+            ResamplingFunction::Lanczos3,
+        )
+        .and_then(|plan| plan.with_threading(threading.clone()))
+        .unwrap();
+        let mut resized16 = vec![0u16; expected16.len()];
+        plan16.resize(&source16, &mut resized16).unwrap();
+        assert_eq!(resized16, expected16, "{threading:?}");
+    }
+}
+
+#[test]
+fn linearization_with_threading_matches_global_pool() {
+    let size = ImageSize::new(256, 256);
+    let mut expected = pattern::<u16>(size, 3);
+    let mut converted = expected.clone();
+    image16_to_linear16::<3>(&mut expected, 10, TransferFunction::Srgb);
+    image16_to_linear16_with_threading::<3>(
+        &mut converted,
+        10,
+        TransferFunction::Srgb,
+        &ThreadingPolicy::Threads(2),
+    )
+    .unwrap();
+    assert_eq!(converted, expected);
+}
+
+#[test]
+fn zero_threads_are_rejected() {
+    let plan = ResizePlan::<u8>::new(
+        ImageSize::new(8, 8),
+        ImageSize::new(4, 4),
+        ChannelLayout::Rgba,
+        ResamplingFunction::Bilinear,
+    )
+    .unwrap();
+    assert_eq!(
+        plan.with_threading(ThreadingPolicy::Threads(0)).err(),
+        Some(PicScaleError::InvalidThreading)
+    );
+}
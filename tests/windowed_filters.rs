@@ -0,0 +1,98 @@
+use pic_scale_safe::{resize_plane_f32, ImageSize, ResamplingFunction};
+
+fn source() -> Vec<f32> {
+    (0..23 * 3)
+        .map(|i| ((i * 37 % 101) as f32) / 100.)
+        .collect()
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (&a, &e)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!((a - e).abs() < 1e-5, "sample {i}: {a} != {e}");
+    }
+}
+
+/// Reference output of the windowed filters,
+/// window is cut by the distance before kernel scaling on downscale.
+///
+/// Destination pixel 3 of every row lands exactly on source pixel 11,
+/// so its zero distance tap is weighted by the jinc limit at zero.
+#[test]
+fn ginseng_downscale_matches_reference() {
+    let resized = resize_plane_f32(
+        &source(),
+        ImageSize::new(23, 3),
+        ImageSize::new(7, 3),
+        ResamplingFunction::Ginseng,
+    )
+    .unwrap();
+    assert_close(
+        &resized,
+        &[
+            0.33789247, 0.47775084, 0.57884806, 0.40774038, 0.4911519, 0.5922492, 0.5010206,
+            0.51988703, 0.57613266, 0.5735253, 0.46, 0.5672578, 0.43926504, 0.40011296, 0.5939476,
+            0.50963, 0.4288481, 0.51225954, 0.57366264, 0.44224918, 0.5821075,
+        ],
+    );
+}
+
+#[test]
+fn haasn_soft_downscale_matches_reference() {
+    let resized = resize_plane_f32(
+        &source(),
+        ImageSize::new(23, 3),
+        ImageSize::new(7, 3),
+        ResamplingFunction::HaasnSoft,
+    )
+    .unwrap();
+    assert_close(
+        &resized,
+        &[
+            0.42980155,
+            0.62918353,
+            0.831106,
+            0.095857754,
+            0.23889427,
+            0.44081643,
+            0.63268495,
+            0.6890459,
+            0.6246554,
+            0.5483571,
+            0.45999998,
+            0.6688943,
+            0.2953445,
+            0.23095378,
+            0.2873151,
+            0.47918352,
+            0.68110603,
+            0.8241422,
+            0.80164236,
+            0.29081643,
+            0.4901986,
+        ],
+    );
+}
+
+/// On 3x upscale every third destination pixel lands exactly on a source pixel,
+/// the tap at zero distance must keep the largest weight
+#[test]
+fn aligned_tap_keeps_impulse_peak() {
+    let mut impulse = vec![0f32; 23];
+    impulse[11] = 1.;
+    for function in [ResamplingFunction::Ginseng, ResamplingFunction::HaasnSoft] {
+        let resized = resize_plane_f32(
+            &impulse,
+            ImageSize::new(23, 1),
+            ImageSize::new(69, 1),
+            function,
+        )
+        .unwrap();
+        let peak = resized
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i);
+        assert_eq!(peak, Some(11 * 3 + 1), "{function:?}");
+    }
+}
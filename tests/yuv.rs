@@ -0,0 +1,299 @@
+use pic_scale_safe::{
+    resize_yuv16, resize_yuv8, resize_yuv_nv16, resize_yuv_nv8, ChannelLayout, ChromaSiting,
+    ImageSize, ResamplingFunction, ResizeOptions, ResizePlan, ScaleTransform, YuvBitPacking,
+    YuvPlanarImage, YuvPlanarImageMut, YuvSemiPlanarImage, YuvSemiPlanarImageMut, YuvSubsampling,
+};
+
+const SITINGS: [ChromaSiting; 3] = [
+    ChromaSiting::Left,
+    ChromaSiting::Center,
+    ChromaSiting::TopLeft,
+];
+
+const SUBSAMPLINGS: [YuvSubsampling; 3] = [
+    YuvSubsampling::Yuv420,
+    YuvSubsampling::Yuv422,
+    YuvSubsampling::Yuv444,
+];
+
+fn sizes() -> [(ImageSize, ImageSize); 3] {
+    [
+        (ImageSize::new(64, 48), ImageSize::new(40, 30)),
+        (ImageSize::new(36, 20), ImageSize::new(90, 50)),
+        (ImageSize::new(33, 17), ImageSize::new(20, 41)),
+    ]
+}
+
+/// Subsampling factors and whether chroma is co-sited with luma, per axis
+fn layout(subsampling: YuvSubsampling, siting: ChromaSiting) -> [(f64, bool); 2] {
+    let (x_factor, y_factor) = match subsampling {
+        YuvSubsampling::Yuv420 => (2., 2.),
+        YuvSubsampling::Yuv422 => (2., 1.),
+        YuvSubsampling::Yuv444 => (1., 1.),
+    };
+    let (x_co_sited, y_co_sited) = match siting {
+        ChromaSiting::Left => (true, false),
+        ChromaSiting::Center => (false, false),
+        ChromaSiting::TopLeft => (true, true),
+    };
+    [(x_factor, x_co_sited), (y_factor, y_co_sited)]
+}
+
+/// Center of chroma sample `j` in luma coordinates, co-sited chroma lays on its first luma
+fn chroma_position(j: usize, (factor, co_sited): (f64, bool)) -> f64 {
+    if co_sited {
+        factor * j as f64 + 0.5
+    } else {
+        factor * (j as f64 + 0.5)
+    }
+}
+
+/// Magnification and offset placing destination chroma over the source chroma,
+/// found by mapping destination chroma samples through luma coordinates
+fn chroma_transform(source_luma: usize, destination_luma: usize, axis: (f64, bool)) -> (f32, f32) {
+    let ratio = source_luma as f64 / destination_luma as f64;
+    // Source chroma pixel `u` spans `u..u + 1`, so its center is at `u + 0.5`
+    let source_chroma =
+        |j: usize| (chroma_position(j, axis) * ratio - chroma_position(0, axis)) / axis.0 + 0.5;
+    let step = source_chroma(1) - source_chroma(0);
+    ((1. / step) as f32, (source_chroma(0) - step / 2.) as f32)
+}
+
+/// Chroma is resampled as a plane placed over the source one by luma geometry and siting
+#[test]
+fn chroma_siting_follows_luma_geometry() {
+    for (source_size, destination_size) in sizes() {
+        for subsampling in SUBSAMPLINGS {
+            for siting in SITINGS {
+                let source_chroma = subsampling.chroma_size(source_size);
+                let destination_chroma = subsampling.chroma_size(destination_size);
+                let y_plane = vec![0u16; source_size.width * source_size.height];
+                let u_plane = (0..source_chroma.width * source_chroma.height)
+                    .map(|i| (i * 4099 % 65536) as u16)
+                    .collect::<Vec<_>>();
+                let v_plane = u_plane.iter().map(|&v| 65535 - v).collect::<Vec<_>>();
+                let mut y_resized = vec![0u16; destination_size.width * destination_size.height];
+                let mut u_resized =
+                    vec![0u16; destination_chroma.width * destination_chroma.height];
+                let mut v_resized = u_resized.clone();
+                resize_yuv16(
+                    &YuvPlanarImage {
+                        y_plane: &y_plane,
+                        y_stride: source_size.width,
+                        u_plane: &u_plane,
+                        u_stride: source_chroma.width,
+                        v_plane: &v_plane,
+                        v_stride: source_chroma.width,
+                        size: source_size,
+                    },
+                    &mut YuvPlanarImageMut {
+                        y_plane: &mut y_resized,
+                        y_stride: destination_size.width,
+                        u_plane: &mut u_resized,
+                        u_stride: destination_chroma.width,
+                        v_plane: &mut v_resized,
+                        v_stride: destination_chroma.width,
+                        size: destination_size,
+                    },
+                    subsampling,
+                    siting,
+                    16,
+                    ResamplingFunction::Bilinear,
+                )
+                .unwrap();
+
+                let [x_axis, y_axis] = layout(subsampling, siting);
+                let (scale_x, offset_x) =
+                    chroma_transform(source_size.width, destination_size.width, x_axis);
+                let (scale_y, offset_y) =
+                    chroma_transform(source_size.height, destination_size.height, y_axis);
+                let plan = ResizePlan::<u16>::with_options(
+                    source_chroma,
+                    destination_chroma,
+                    ChannelLayout::Plane,
+                    16,
+                    ResizeOptions::new(ResamplingFunction::Bilinear).with_scale_transform(
+                        ScaleTransform::new(scale_x, scale_y, offset_x, offset_y),
+                    ),
+                )
+                .unwrap();
+                for (plane, resized) in [(&u_plane, &u_resized), (&v_plane, &v_resized)] {
+                    let mut expected = vec![0u16; resized.len()];
+                    plan.resize(plane, &mut expected).unwrap();
+                    for (i, (&resized, &expected)) in
+                        resized.iter().zip(expected.iter()).enumerate()
+                    {
+                        assert!(
+                            resized.abs_diff(expected) <= 1,
+                            "{subsampling:?} {siting:?} {source_size:?} -> {destination_size:?}, \
+                             {i}: {resized} != {expected}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn interleave<T: Copy>(u: &[T], v: &[T]) -> Vec<T> {
+    u.iter().zip(v.iter()).flat_map(|(&u, &v)| [u, v]).collect()
+}
+
+/// NV12 style interleaved chroma is resampled as planar one
+#[test]
+fn semi_planar8_matches_planar() {
+    for (source_size, destination_size) in sizes() {
+        for subsampling in [YuvSubsampling::Yuv420, YuvSubsampling::Yuv422] {
+            let source_chroma = subsampling.chroma_size(source_size);
+            let destination_chroma = subsampling.chroma_size(destination_size);
+            let y_plane = (0..source_size.width * source_size.height)
+                .map(|i| (i * 7 % 256) as u8)
+                .collect::<Vec<_>>();
+            let u_plane = (0..source_chroma.width * source_chroma.height)
+                .map(|i| (i * 13 % 256) as u8)
+                .collect::<Vec<_>>();
+            let v_plane = u_plane.iter().map(|&v| 255 - v).collect::<Vec<_>>();
+            let uv_plane = interleave(&u_plane, &v_plane);
+
+            let mut y_planar = vec![0u8; destination_size.width * destination_size.height];
+            let mut u_planar = vec![0u8; destination_chroma.width * destination_chroma.height];
+            let mut v_planar = u_planar.clone();
+            resize_yuv8(
+                &YuvPlanarImage {
+                    y_plane: &y_plane,
+                    y_stride: source_size.width,
+                    u_plane: &u_plane,
+                    u_stride: source_chroma.width,
+                    v_plane: &v_plane,
+                    v_stride: source_chroma.width,
+                    size: source_size,
+                },
+                &mut YuvPlanarImageMut {
+                    y_plane: &mut y_planar,
+                    y_stride: destination_size.width,
+                    u_plane: &mut u_planar,
+                    u_stride: destination_chroma.width,
+                    v_plane: &mut v_planar,
+                    v_stride: destination_chroma.width,
+                    size: destination_size,
+                },
+                subsampling,
+                ChromaSiting::Left,
+                ResamplingFunction::Lanczos3,
+            )
+            .unwrap();
+
+            let mut y_nv = vec![0u8; y_planar.len()];
+            let mut uv_nv = vec![0u8; u_planar.len() * 2];
+            resize_yuv_nv8(
+                &YuvSemiPlanarImage {
+                    y_plane: &y_plane,
+                    y_stride: source_size.width,
+                    uv_plane: &uv_plane,
+                    uv_stride: source_chroma.width * 2,
+                    size: source_size,
+                },
+                &mut YuvSemiPlanarImageMut {
+                    y_plane: &mut y_nv,
+                    y_stride: destination_size.width,
+                    uv_plane: &mut uv_nv,
+                    uv_stride: destination_chroma.width * 2,
+                    size: destination_size,
+                },
+                subsampling,
+                ChromaSiting::Left,
+                ResamplingFunction::Lanczos3,
+            )
+            .unwrap();
+
+            assert_eq!(y_nv, y_planar);
+            assert_eq!(uv_nv, interleave(&u_planar, &v_planar));
+        }
+    }
+}
+
+/// P010 keeps 10 significant bits in the high bits of each sample
+#[test]
+fn p010_matches_planar_10_bit() {
+    let subsampling = YuvSubsampling::Yuv420;
+    for (source_size, destination_size) in sizes() {
+        let source_chroma = subsampling.chroma_size(source_size);
+        let destination_chroma = subsampling.chroma_size(destination_size);
+        let y_plane = (0..source_size.width * source_size.height)
+            .map(|i| (i * 37 % 1024) as u16)
+            .collect::<Vec<_>>();
+        let u_plane = (0..source_chroma.width * source_chroma.height)
+            .map(|i| (i * 59 % 1024) as u16)
+            .collect::<Vec<_>>();
+        let v_plane = u_plane.iter().map(|&v| 1023 - v).collect::<Vec<_>>();
+
+        let mut y_planar = vec![0u16; destination_size.width * destination_size.height];
+        let mut u_planar = vec![0u16; destination_chroma.width * destination_chroma.height];
+        let mut v_planar = u_planar.clone();
+        resize_yuv16(
+            &YuvPlanarImage {
+                y_plane: &y_plane,
+                y_stride: source_size.width,
+                u_plane: &u_plane,
+                u_stride: source_chroma.width,
+                v_plane: &v_plane,
+                v_stride: source_chroma.width,
+                size: source_size,
+            },
+            &mut YuvPlanarImageMut {
+                y_plane: &mut y_planar,
+                y_stride: destination_size.width,
+                u_plane: &mut u_planar,
+                u_stride: destination_chroma.width,
+                v_plane: &mut v_planar,
+                v_stride: destination_chroma.width,
+                size: destination_size,
+            },
+            subsampling,
+            ChromaSiting::Left,
+            10,
+            ResamplingFunction::Bilinear,
+        )
+        .unwrap();
+
+        let msb = |plane: &[u16]| plane.iter().map(|&v| v << 6).collect::<Vec<_>>();
+        let y_p010 = msb(&y_plane);
+        let uv_p010 = msb(&interleave(&u_plane, &v_plane));
+        let mut y_resized = vec![0u16; y_planar.len()];
+        let mut uv_resized = vec![0u16; u_planar.len() * 2];
+        resize_yuv_nv16(
+            &YuvSemiPlanarImage {
+                y_plane: &y_p010,
+                y_stride: source_size.width,
+                uv_plane: &uv_p010,
+                uv_stride: source_chroma.width * 2,
+                size: source_size,
+            },
+            &mut YuvSemiPlanarImageMut {
+                y_plane: &mut y_resized,
+                y_stride: destination_size.width,
+                uv_plane: &mut uv_resized,
+                uv_stride: destination_chroma.width * 2,
+                size: destination_size,
+            },
+            subsampling,
+            ChromaSiting::Left,
+            10,
+            YuvBitPacking::Msb,
+            ResamplingFunction::Bilinear,
+        )
+        .unwrap();
+
+        let expected_uv = interleave(&u_planar, &v_planar);
+        for (resized, expected) in [(&y_resized, &y_planar), (&uv_resized, &expected_uv)] {
+            for (&p010, &planar) in resized.iter().zip(expected.iter()) {
+                assert_eq!(p010 & 0x3f, 0, "low bits must stay zero");
+                assert!(
+                    (p010 >> 6).abs_diff(planar) <= 1,
+                    "{} != {planar}",
+                    p010 >> 6
+                );
+            }
+        }
+    }
+}